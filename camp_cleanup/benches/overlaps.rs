@@ -0,0 +1,69 @@
+use camp_cleanup::interval::Interval;
+use camp_cleanup::overlaps::{
+    naive_covering, naive_overlapping_pairs, overlapping_pairs, IntervalTree,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use utils::Rng;
+
+fn generate(assignments: usize, rng: &mut Rng) -> Vec<Interval> {
+    (0..assignments)
+        .map(|_| {
+            let start = 1 + rng.below(10_000_000) as i64;
+            Interval::new(start, start + rng.below(100) as i64)
+        })
+        .collect()
+}
+
+/// The sweep line and interval tree on generated assignments, and the naive versions only on
+/// the smaller input since they are quadratic.
+fn overlaps(c: &mut Criterion) {
+    let mut rng = Rng::new(2022);
+    let mut group = c.benchmark_group("overlaps");
+    group.sample_size(10);
+    for assignments in [20_000, 200_000] {
+        let intervals = generate(assignments, &mut rng);
+        let sections: Vec<i64> = intervals.iter().map(|i| i.start + 1).collect();
+        group.bench_with_input(
+            BenchmarkId::new("pairs sweep", assignments),
+            &intervals,
+            |b, intervals| b.iter(|| overlapping_pairs(intervals)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("covering tree", assignments),
+            &intervals,
+            |b, intervals| {
+                b.iter(|| {
+                    let tree = IntervalTree::new(intervals);
+                    sections
+                        .iter()
+                        .map(|s| tree.covering(*s).len())
+                        .sum::<usize>()
+                })
+            },
+        );
+        if assignments > 20_000 {
+            continue;
+        }
+        group.bench_with_input(
+            BenchmarkId::new("pairs naive", assignments),
+            &intervals,
+            |b, intervals| b.iter(|| naive_overlapping_pairs(intervals)),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("covering naive", assignments),
+            &intervals,
+            |b, intervals| {
+                b.iter(|| {
+                    sections
+                        .iter()
+                        .map(|s| naive_covering(intervals, *s).len())
+                        .sum::<usize>()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, overlaps);
+criterion_main!(benches);
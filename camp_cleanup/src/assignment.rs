@@ -0,0 +1,43 @@
+use std::fmt;
+
+use crate::interval::Interval;
+
+/// One elf's section assignment, `elf` being 0 or 1 for the left or right side of the line.
+#[derive(Debug, Copy, Clone)]
+pub struct Assignment {
+    pub line: usize,
+    pub elf: usize,
+    pub sections: Interval,
+}
+
+impl Assignment {
+    pub fn from_lines(lines: &[String]) -> Vec<Assignment> {
+        lines
+            .iter()
+            .enumerate()
+            .flat_map(|(index, line)| {
+                let (i1, i2) = line.split_once(',').unwrap();
+                [i1, i2]
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(elf, sections)| Assignment {
+                        line: index + 1,
+                        elf,
+                        sections: Interval::from(sections),
+                    })
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {} elf {} ({})",
+            self.line,
+            self.elf + 1,
+            self.sections
+        )
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+
+use crate::assignment::Assignment;
+use crate::interval::{Interval, IntervalSet};
+
+/// Number of elves cleaning each section, as maximal runs of sections with the same count.
+pub struct Coverage {
+    segments: Vec<(Interval, usize)>,
+}
+
+impl Coverage {
+    pub fn new(assignments: &[Assignment]) -> Self {
+        let mut events: Vec<(i64, i64)> = assignments
+            .iter()
+            .flat_map(|a| [(a.sections.start, 1), (a.sections.end + 1, -1)])
+            .collect();
+        events.sort();
+
+        let mut segments: Vec<(Interval, usize)> = vec![];
+        let mut count = 0;
+        for (index, (position, delta)) in events.iter().enumerate() {
+            count += delta;
+            match events.get(index + 1) {
+                Some((next, _)) if next > position && count > 0 => {
+                    // one elf ending right where another starts leaves the count unchanged
+                    match segments.last_mut() {
+                        Some((last, last_count))
+                            if last.end + 1 == *position && *last_count == count as usize =>
+                        {
+                            last.end = next - 1
+                        }
+                        _ => segments.push((Interval::new(*position, next - 1), count as usize)),
+                    }
+                }
+                _ => {}
+            }
+        }
+        Coverage { segments }
+    }
+
+    pub fn covered(&self) -> IntervalSet {
+        self.segments
+            .iter()
+            .map(|(interval, _)| *interval)
+            .collect()
+    }
+
+    /// Uncovered sections between the first and last covered section.
+    pub fn gaps(&self) -> IntervalSet {
+        let (first, last) = match (self.segments.first(), self.segments.last()) {
+            (Some((first, _)), Some((last, _))) => (first.start, last.end),
+            _ => return IntervalSet::new(),
+        };
+        let mut gaps: IntervalSet = [Interval::new(first, last)].into_iter().collect();
+        self.segments
+            .iter()
+            .for_each(|(interval, _)| gaps.remove(*interval));
+        gaps
+    }
+
+    pub fn cleaned_more_than(&self, elves: usize) -> IntervalSet {
+        self.segments
+            .iter()
+            .filter(|(_, count)| *count > elves)
+            .map(|(interval, _)| *interval)
+            .collect()
+    }
+
+    /// Fewest elves cleaning any single section of `interval`.
+    pub fn min_count(&self, interval: &Interval) -> usize {
+        let lo = self
+            .segments
+            .partition_point(|(segment, _)| segment.end < interval.start);
+        let hi = self
+            .segments
+            .partition_point(|(segment, _)| segment.start <= interval.end);
+        let covered: i64 = self.segments[lo..hi]
+            .iter()
+            .filter_map(|(segment, _)| segment.intersection(interval))
+            .map(|part| part.len())
+            .sum();
+        if covered < interval.len() {
+            return 0;
+        }
+        self.segments[lo..hi]
+            .iter()
+            .map(|(_, count)| *count)
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+pub struct CoverageReport {
+    pub covered: IntervalSet,
+    pub gaps: IntervalSet,
+    pub crowded_above: usize,
+    pub crowded: IntervalSet,
+    /// Elf whose every section is cleaned by the most other elves, with that number of elves.
+    pub most_redundant: Option<(Assignment, usize)>,
+}
+
+impl CoverageReport {
+    pub fn new(assignments: &[Assignment], crowded_above: usize) -> Self {
+        let coverage = Coverage::new(assignments);
+        let most_redundant = assignments
+            .iter()
+            .map(|a| (*a, coverage.min_count(&a.sections) - 1))
+            .filter(|(_, others)| *others > 0)
+            .max_by(|(a, others_a), (b, others_b)| {
+                others_a
+                    .cmp(others_b)
+                    .then(a.sections.len().cmp(&b.sections.len()))
+                    .then((b.line, b.elf).cmp(&(a.line, a.elf)))
+            });
+        CoverageReport {
+            covered: coverage.covered(),
+            gaps: coverage.gaps(),
+            crowded_above,
+            crowded: coverage.cleaned_more_than(crowded_above),
+            most_redundant,
+        }
+    }
+}
+
+fn ranges(set: &IntervalSet) -> String {
+    let ranges: Vec<String> = set.ranges().iter().map(|r| r.to_string()).collect();
+    if ranges.is_empty() {
+        "none".to_string()
+    } else {
+        ranges.join(", ")
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Covered sections ({}): {}",
+            self.covered.len(),
+            ranges(&self.covered)
+        )?;
+        writeln!(
+            f,
+            "Uncovered gaps ({}): {}",
+            self.gaps.len(),
+            ranges(&self.gaps)
+        )?;
+        writeln!(
+            f,
+            "Cleaned by more than {} elves ({}): {}",
+            self.crowded_above,
+            self.crowded.len(),
+            ranges(&self.crowded)
+        )?;
+        match &self.most_redundant {
+            Some((assignment, others)) => writeln!(
+                f,
+                "Most redundant elf: {}, every section also cleaned by at least {} other elves",
+                assignment, others
+            ),
+            None => writeln!(f, "Most redundant elf: none, every elf is needed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assignments(lines: &[&str]) -> Vec<Assignment> {
+        Assignment::from_lines(
+            &lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn set(ranges: &[(i64, i64)]) -> IntervalSet {
+        ranges
+            .iter()
+            .map(|(start, end)| Interval::new(*start, *end))
+            .collect()
+    }
+
+    #[test]
+    fn touching_elves_leave_no_gap() {
+        let coverage = Coverage::new(&assignments(&["1-3,4-6"]));
+        assert_eq!(coverage.segments, [(Interval::new(1, 6), 1)]);
+        assert_eq!(coverage.covered(), set(&[(1, 6)]));
+        assert!(coverage.gaps().is_empty());
+        assert_eq!(coverage.min_count(&Interval::new(3, 4)), 1);
+    }
+
+    #[test]
+    fn counts_sections_around_a_gap() {
+        let coverage = Coverage::new(&assignments(&["1-3,4-6", "10-12,2-5"]));
+        assert_eq!(coverage.covered(), set(&[(1, 6), (10, 12)]));
+        assert_eq!(coverage.gaps(), set(&[(7, 9)]));
+        assert_eq!(coverage.cleaned_more_than(1), set(&[(2, 5)]));
+        assert!(coverage.cleaned_more_than(2).is_empty());
+
+        assert_eq!(coverage.min_count(&Interval::new(2, 5)), 2);
+        assert_eq!(coverage.min_count(&Interval::new(1, 3)), 1);
+        // spanning the gap, or lying inside it, means some section has nobody
+        assert_eq!(coverage.min_count(&Interval::new(5, 11)), 0);
+        assert_eq!(coverage.min_count(&Interval::new(7, 8)), 0);
+        assert_eq!(coverage.min_count(&Interval::new(20, 30)), 0);
+    }
+
+    #[test]
+    fn finds_the_most_redundant_elf() {
+        let report = CoverageReport::new(&assignments(&["1-3,4-6", "10-12,2-5"]), 1);
+        let (elf, others) = report.most_redundant.unwrap();
+        assert_eq!((elf.line, elf.elf, others), (2, 1, 1));
+
+        let report = CoverageReport::new(&assignments(&["1-3,4-6"]), 1);
+        assert!(report.most_redundant.is_none());
+    }
+}
@@ -0,0 +1,272 @@
+use std::fmt::{self, Debug, Display};
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+/// Integer types an `Interval` can be built over.
+pub trait Bound:
+    Copy + Ord + Debug + Display + FromStr + Add<Output = Self> + Sub<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+}
+
+macro_rules! bound {
+    ($($t:ty),*) => {
+        $(impl Bound for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+        })*
+    };
+}
+
+bound!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// Closed interval `start..=end`, never empty.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval<T = i64> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        if start > end {
+            panic!("interval {}-{} is empty", start, end);
+        }
+        Interval { start, end }
+    }
+
+    pub fn from(interval_string: &str) -> Self
+    where
+        T::Err: Debug,
+    {
+        let (left, right) = interval_string.split_once('-').unwrap();
+        Interval::new(left.parse().unwrap(), right.parse().unwrap())
+    }
+
+    pub fn len(&self) -> T {
+        self.end - self.start + T::ONE
+    }
+
+    pub fn contains_point(&self, point: T) -> bool {
+        self.start <= point && point <= self.end
+    }
+
+    pub fn contains(&self, interval: &Interval<T>) -> bool {
+        interval.start >= self.start && interval.end <= self.end
+    }
+
+    pub fn overlaps(&self, interval: &Interval<T>) -> bool {
+        self.start <= interval.end && interval.start <= self.end
+    }
+
+    /// True when the intervals do not overlap but no integer lies between them.
+    pub fn is_adjacent(&self, interval: &Interval<T>) -> bool {
+        (self.end < interval.start && interval.start - self.end == T::ONE)
+            || (interval.end < self.start && self.start - interval.end == T::ONE)
+    }
+
+    pub fn intersection(&self, interval: &Interval<T>) -> Option<Interval<T>> {
+        if self.overlaps(interval) {
+            Some(Interval {
+                start: self.start.max(interval.start),
+                end: self.end.min(interval.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Single interval covering both, if they overlap or are adjacent.
+    pub fn union(&self, interval: &Interval<T>) -> Option<Interval<T>> {
+        if self.overlaps(interval) || self.is_adjacent(interval) {
+            Some(Interval {
+                start: self.start.min(interval.start),
+                end: self.end.max(interval.end),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Parts of `self` not covered by `interval`: zero, one or two intervals.
+    pub fn difference(&self, interval: &Interval<T>) -> Vec<Interval<T>> {
+        if !self.overlaps(interval) {
+            return vec![*self];
+        }
+        let mut parts = vec![];
+        if self.start < interval.start {
+            parts.push(Interval {
+                start: self.start,
+                end: interval.start - T::ONE,
+            });
+        }
+        if interval.end < self.end {
+            parts.push(Interval {
+                start: interval.end + T::ONE,
+                end: self.end,
+            });
+        }
+        parts
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Sorted, disjoint and non-adjacent intervals; touching ranges are merged on insert.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntervalSet<T = i64> {
+    ranges: Vec<Interval<T>>,
+}
+
+impl<T: Bound> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: vec![] }
+    }
+
+    pub fn ranges(&self) -> &[Interval<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        let lo = self
+            .ranges
+            .partition_point(|r| r.end < interval.start && !r.is_adjacent(&interval));
+        let hi = self
+            .ranges
+            .partition_point(|r| r.start <= interval.end || r.is_adjacent(&interval));
+        let merged = self.ranges[lo..hi]
+            .iter()
+            .fold(interval, |acc, r| acc.union(r).unwrap());
+        self.ranges.splice(lo..hi, [merged]);
+    }
+
+    pub fn remove(&mut self, interval: Interval<T>) {
+        let lo = self.ranges.partition_point(|r| r.end < interval.start);
+        let hi = self.ranges.partition_point(|r| r.start <= interval.end);
+        let remaining: Vec<Interval<T>> = self.ranges[lo..hi]
+            .iter()
+            .flat_map(|r| r.difference(&interval))
+            .collect();
+        self.ranges.splice(lo..hi, remaining);
+    }
+
+    pub fn contains_point(&self, point: T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end < point);
+        self.ranges
+            .get(index)
+            .is_some_and(|r| r.contains_point(point))
+    }
+
+    /// True when every point of `interval` is in the set.
+    pub fn covers(&self, interval: &Interval<T>) -> bool {
+        let index = self.ranges.partition_point(|r| r.end < interval.start);
+        self.ranges.get(index).is_some_and(|r| r.contains(interval))
+    }
+
+    pub fn overlaps(&self, interval: &Interval<T>) -> bool {
+        let index = self.ranges.partition_point(|r| r.end < interval.start);
+        self.ranges.get(index).is_some_and(|r| r.overlaps(interval))
+    }
+
+    /// Parts of the set that fall inside `interval`.
+    pub fn query(&self, interval: &Interval<T>) -> Vec<Interval<T>> {
+        let lo = self.ranges.partition_point(|r| r.end < interval.start);
+        let hi = self.ranges.partition_point(|r| r.start <= interval.end);
+        self.ranges[lo..hi]
+            .iter()
+            .filter_map(|r| r.intersection(interval))
+            .collect()
+    }
+
+    /// Number of points covered, counting each point once.
+    pub fn len(&self) -> T {
+        self.ranges.iter().fold(T::ZERO, |total, r| total + r.len())
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        iter.into_iter().for_each(|interval| set.insert(interval));
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[(i64, i64)]) -> IntervalSet {
+        ranges
+            .iter()
+            .map(|(start, end)| Interval::new(*start, *end))
+            .collect()
+    }
+
+    #[test]
+    fn insert_merges_overlapping_and_adjacent_ranges() {
+        let mut ranges = set(&[(1, 2), (6, 7), (10, 12)]);
+        ranges.insert(Interval::new(3, 5));
+        assert_eq!(ranges, set(&[(1, 7), (10, 12)]));
+        ranges.insert(Interval::new(9, 9));
+        assert_eq!(ranges.ranges(), [Interval::new(1, 7), Interval::new(9, 12)]);
+        ranges.insert(Interval::new(0, 20));
+        assert_eq!(ranges, set(&[(0, 20)]));
+        assert_eq!(ranges.len(), 21);
+    }
+
+    #[test]
+    fn remove_splits_a_range() {
+        let mut ranges = set(&[(1, 10), (20, 30)]);
+        ranges.remove(Interval::new(4, 6));
+        assert_eq!(ranges, set(&[(1, 3), (7, 10), (20, 30)]));
+        ranges.remove(Interval::new(8, 25));
+        assert_eq!(ranges, set(&[(1, 3), (7, 7), (26, 30)]));
+        ranges.remove(Interval::new(0, 40));
+        assert!(ranges.is_empty());
+    }
+
+    #[test]
+    fn queries() {
+        let ranges = set(&[(1, 3), (7, 10), (20, 30)]);
+        assert_eq!(
+            ranges.query(&Interval::new(2, 21)),
+            [
+                Interval::new(2, 3),
+                Interval::new(7, 10),
+                Interval::new(20, 21)
+            ]
+        );
+        assert!(ranges.query(&Interval::new(4, 6)).is_empty());
+
+        assert!(ranges.covers(&Interval::new(7, 10)));
+        assert!(!ranges.covers(&Interval::new(3, 7)));
+        assert!(ranges.overlaps(&Interval::new(3, 7)));
+        assert!(!ranges.overlaps(&Interval::new(11, 19)));
+        assert!(ranges.contains_point(20) && !ranges.contains_point(19));
+    }
+
+    #[test]
+    fn interval_containment() {
+        let outer = Interval::new(2, 8);
+        assert!(outer.contains(&Interval::new(2, 8)));
+        assert!(outer.contains(&Interval::new(3, 7)));
+        assert!(!outer.contains(&Interval::new(1, 7)));
+        assert!(!Interval::new(3, 7).contains(&outer));
+    }
+}
@@ -0,0 +1,5 @@
+pub mod assignment;
+pub mod coverage;
+pub mod interval;
+pub mod overlaps;
+pub mod reassign;
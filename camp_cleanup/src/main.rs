@@ -1,54 +1,97 @@
 use std::fs::File;
 use utils::read_lines;
 
-struct Interval {
-    start: i64,
-    end: i64,
-}
+use camp_cleanup::assignment::Assignment;
+use camp_cleanup::coverage::CoverageReport;
+use camp_cleanup::interval::Interval;
+use camp_cleanup::overlaps::{overlapping_pairs, IntervalTree};
+use camp_cleanup::reassign;
 
-impl Interval {
-    pub fn from(interval_string: &str) -> Self {
-        let (left, right) = interval_string.split_once("-").unwrap();
-        return Interval {
-            start: left.parse().unwrap(),
-            end: right.parse().unwrap(),
-        };
-    }
+fn main() {
+    let file = File::open("./camp_cleanup/input/input.txt").unwrap();
+    let lines = read_lines(file);
 
-    pub fn contains(&self, interval: &Interval) -> bool {
-        interval.start >= self.start && interval.end <= self.end
-    }
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(|arg| arg.as_str()) {
+        Some("coverage") => {
+            let crowded_above = args.get(2).map_or(1, |k| k.parse().unwrap());
+            let assignments = Assignment::from_lines(&lines);
+            print!("{}", CoverageReport::new(&assignments, crowded_above));
+            return;
+        }
+        Some("overlaps") => {
+            let assignments = Assignment::from_lines(&lines);
+            let intervals: Vec<Interval> = assignments.iter().map(|a| a.sections).collect();
+            let pairs = overlapping_pairs(&intervals);
+            let across_lines = pairs
+                .iter()
+                .filter(|(i, j)| assignments[*i].line != assignments[*j].line)
+                .count();
+            println!(
+                "Overlapping assignment pairs: {:?} ({:?} between different lines)",
+                pairs.len(),
+                across_lines
+            );
+            return;
+        }
+        Some("covering") => {
+            let section: i64 = args[2].parse().unwrap();
+            let assignments = Assignment::from_lines(&lines);
+            let intervals: Vec<Interval> = assignments.iter().map(|a| a.sections).collect();
+            let covering = IntervalTree::new(&intervals).covering(section);
+            covering
+                .iter()
+                .for_each(|id| println!("{}", assignments[*id]));
+            println!("Section {} is covered by {} elves", section, covering.len());
+            return;
+        }
+        Some("reassign") => {
+            let assignments = Assignment::from_lines(&lines);
+            let intervals: Vec<Interval> = assignments.iter().map(|a| a.sections).collect();
+            let reassignment = reassign::reassign(&intervals);
 
-    pub fn overlaps(&self, interval: &Interval) -> bool {
-        (self.start <= interval.start && interval.start <= self.end)
-            || (self.start <= interval.end && interval.end <= self.end)
-            || interval.contains(self)
+            // one line per input line, `-` standing for an elf that is no longer needed
+            for ranges in reassignment.ranges.chunks(2) {
+                let ranges: Vec<String> = ranges
+                    .iter()
+                    .map(|range| range.map_or("-".to_string(), |range| range.to_string()))
+                    .collect();
+                println!("{}", ranges.join(","));
+            }
+            println!();
+            println!(
+                "{} of {} elves keep their assignment, {} are no longer needed",
+                reassignment.unchanged(&intervals),
+                intervals.len(),
+                reassignment
+                    .ranges
+                    .iter()
+                    .filter(|range| range.is_none())
+                    .count()
+            );
+            return;
+        }
+        _ => {}
     }
-}
 
-fn main() {
-    let file = File::open("./camp_cleanup/input/input.txt").unwrap();
-    let lines = read_lines(file);
-    let fully_contained = lines
+    let pairs: Vec<(Interval, Interval)> = lines
         .iter()
         .map(|line| {
-            let (i1, i2) = line.split_once(",").unwrap();
-            let (i1, i2) = (Interval::from(i1), Interval::from(i2));
-            i1.contains(&i2) || i2.contains(&i1)
+            let (i1, i2) = line.split_once(',').unwrap();
+            (Interval::from(i1), Interval::from(i2))
         })
-        .filter(|contained| *contained)
+        .collect();
+
+    let fully_contained = pairs
+        .iter()
+        .filter(|(i1, i2)| i1.contains(i2) || i2.contains(i1))
         .count();
 
     println!("Part 1 - fully contained intervals: {:?}", fully_contained);
 
-    let ovelapped = lines
+    let ovelapped = pairs
         .iter()
-        .map(|line| {
-            let (i1, i2) = line.split_once(",").unwrap();
-            let (i1, i2) = (Interval::from(i1), Interval::from(i2));
-            i1.overlaps(&i2)
-        })
-        .filter(|contained| *contained)
+        .filter(|(i1, i2)| i1.intersection(i2).is_some())
         .count();
 
     println!("Part 2 - overlapped intervals: {:?}", ovelapped);
@@ -0,0 +1,200 @@
+use std::collections::BTreeSet;
+
+use crate::interval::Interval;
+
+/// Every pair of intervals `(i, j)`, `i < j`, that share at least one section.
+///
+/// Sweeps intervals by start, keeping the ones still open ordered by end, so the work is
+/// O(n log n) plus the number of pairs reported.
+pub fn overlapping_pairs(intervals: &[Interval]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_by_key(|i| intervals[*i].start);
+
+    let mut open: BTreeSet<(i64, usize)> = BTreeSet::new();
+    let mut pairs = vec![];
+    for i in order {
+        let interval = intervals[i];
+        open = open.split_off(&(interval.start, 0));
+        pairs.extend(open.iter().map(|(_, j)| (i.min(*j), i.max(*j))));
+        open.insert((interval.end, i));
+    }
+    pairs.sort();
+    pairs
+}
+
+/// Compares every interval with every other one, as a reference for `overlapping_pairs`.
+pub fn naive_overlapping_pairs(intervals: &[Interval]) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+    for i in 0..intervals.len() {
+        for j in (i + 1)..intervals.len() {
+            if intervals[i].overlaps(&intervals[j]) {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+struct Node {
+    center: i64,
+    // intervals containing `center`, sorted by ascending start and by descending end
+    by_start: Vec<usize>,
+    by_end: Vec<usize>,
+    left: Option<Box<Node>>,
+    right: Option<Box<Node>>,
+}
+
+/// Centered interval tree answering "which intervals contain section s" in O(log n + k).
+pub struct IntervalTree<'a> {
+    intervals: &'a [Interval],
+    root: Option<Box<Node>>,
+}
+
+impl<'a> IntervalTree<'a> {
+    pub fn new(intervals: &'a [Interval]) -> Self {
+        let root = IntervalTree::build(intervals, (0..intervals.len()).collect());
+        IntervalTree { intervals, root }
+    }
+
+    fn build(intervals: &[Interval], ids: Vec<usize>) -> Option<Box<Node>> {
+        if ids.is_empty() {
+            return None;
+        }
+        let mut endpoints: Vec<i64> = ids
+            .iter()
+            .flat_map(|id| [intervals[*id].start, intervals[*id].end])
+            .collect();
+        let middle = endpoints.len() / 2;
+        let center = *endpoints.select_nth_unstable(middle).1;
+
+        let (mut left, mut right, mut here) = (vec![], vec![], vec![]);
+        for id in ids {
+            let interval = intervals[id];
+            if interval.end < center {
+                left.push(id);
+            } else if interval.start > center {
+                right.push(id);
+            } else {
+                here.push(id);
+            }
+        }
+
+        let mut by_start = here.clone();
+        by_start.sort_by_key(|id| intervals[*id].start);
+        let mut by_end = here;
+        by_end.sort_by_key(|id| std::cmp::Reverse(intervals[*id].end));
+
+        Some(Box::new(Node {
+            center,
+            by_start,
+            by_end,
+            left: IntervalTree::build(intervals, left),
+            right: IntervalTree::build(intervals, right),
+        }))
+    }
+
+    /// Indices of all intervals containing `section`, in ascending order.
+    pub fn covering(&self, section: i64) -> Vec<usize> {
+        let mut found = vec![];
+        let mut node = &self.root;
+        while let Some(current) = node {
+            if section < current.center {
+                found.extend(
+                    current
+                        .by_start
+                        .iter()
+                        .take_while(|id| self.intervals[**id].start <= section),
+                );
+                node = &current.left;
+            } else if section > current.center {
+                found.extend(
+                    current
+                        .by_end
+                        .iter()
+                        .take_while(|id| self.intervals[**id].end >= section),
+                );
+                node = &current.right;
+            } else {
+                found.extend(current.by_start.iter());
+                break;
+            }
+        }
+        found.sort();
+        found
+    }
+}
+
+/// Checks every interval, as a reference for `IntervalTree::covering`.
+pub fn naive_covering(intervals: &[Interval], section: i64) -> Vec<usize> {
+    (0..intervals.len())
+        .filter(|id| intervals[*id].contains_point(section))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::Rng;
+
+    /// Short intervals over few sections, so many share or touch an endpoint or are identical.
+    fn random_intervals(count: usize, rng: &mut Rng) -> Vec<Interval> {
+        (0..count)
+            .map(|_| {
+                let start = 1 + rng.below(20) as i64;
+                Interval::new(start, start + rng.below(5) as i64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pairs_match_the_naive_comparison() {
+        let mut rng = Rng::new(36);
+        for count in 0..60 {
+            let intervals = random_intervals(count, &mut rng);
+            assert_eq!(
+                overlapping_pairs(&intervals),
+                naive_overlapping_pairs(&intervals),
+                "{:?}",
+                intervals
+            );
+        }
+    }
+
+    #[test]
+    fn touching_and_identical_intervals_overlap() {
+        let intervals = [
+            Interval::new(2, 4),
+            Interval::new(4, 6),
+            Interval::new(2, 4),
+            Interval::new(7, 7),
+            Interval::new(6, 6),
+        ];
+        assert_eq!(
+            overlapping_pairs(&intervals),
+            vec![(0, 1), (0, 2), (1, 2), (1, 4)]
+        );
+        let tree = IntervalTree::new(&intervals);
+        assert_eq!(tree.covering(4), vec![0, 1, 2]);
+        assert_eq!(tree.covering(6), vec![1, 4]);
+        assert_eq!(tree.covering(7), vec![3]);
+        assert_eq!(tree.covering(1), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn covering_matches_the_naive_scan() {
+        let mut rng = Rng::new(3636);
+        for count in 0..60 {
+            let intervals = random_intervals(count, &mut rng);
+            let tree = IntervalTree::new(&intervals);
+            for section in 0..=26 {
+                assert_eq!(
+                    tree.covering(section),
+                    naive_covering(&intervals, section),
+                    "section {} of {:?}",
+                    section,
+                    intervals
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::collections::BTreeSet;
+
+use crate::interval::{Interval, IntervalSet};
+
+/// Trimmed assignments where every covered section is cleaned by exactly one elf.
+pub struct Reassignment {
+    /// New sections per elf, `None` when the elf is no longer needed.
+    pub ranges: Vec<Option<Interval>>,
+}
+
+impl Reassignment {
+    pub fn unchanged(&self, original: &[Interval]) -> usize {
+        self.ranges
+            .iter()
+            .zip(original)
+            .filter(|(new, old)| **new == Some(**old))
+            .count()
+    }
+}
+
+/// Largest set of pairwise disjoint assignments, picking by earliest end.
+fn disjoint(intervals: &[Interval]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_by_key(|id| (intervals[*id].end, intervals[*id].start));
+    let mut kept: Vec<usize> = vec![];
+    for id in order {
+        if kept
+            .last()
+            .is_none_or(|last| intervals[*last].end < intervals[id].start)
+        {
+            kept.push(id);
+        }
+    }
+    kept
+}
+
+/// Keeps every elf in `kept` as is and splits the rest of the covered sections between the
+/// other elves, or returns the first section no remaining elf can take.
+///
+/// The sections left over form regions between kept assignments. Each region is filled from
+/// left to right, preferring elves that end before the next region, since nobody else needs
+/// them, and otherwise the elf that reaches the next region but ends soonest. Later sections
+/// only care how far an elf reaches, so no other choice leaves better elves for them, and this
+/// fails only when no split exists at all.
+fn tile(
+    intervals: &[Interval],
+    covered: &IntervalSet,
+    kept: &[usize],
+) -> Result<Vec<Option<Interval>>, i64> {
+    let mut ranges = vec![None; intervals.len()];
+    let mut remaining = covered.clone();
+    for id in kept {
+        ranges[*id] = Some(intervals[*id]);
+        remaining.remove(intervals[*id]);
+    }
+
+    let mut order: Vec<usize> = (0..intervals.len())
+        .filter(|id| ranges[*id].is_none())
+        .collect();
+    order.sort_by_key(|id| intervals[*id].start);
+    let mut order = order.into_iter().peekable();
+    let mut available: BTreeSet<(i64, usize)> = BTreeSet::new();
+
+    let regions = remaining.ranges();
+    for (index, region) in regions.iter().enumerate() {
+        let next_region = regions.get(index + 1).map_or(i64::MAX, |r| r.start);
+        let mut section = region.start;
+        while section <= region.end {
+            while let Some(id) = order.next_if(|id| intervals[*id].start <= section) {
+                available.insert((intervals[id].end, id));
+            }
+            available = available.split_off(&(section, 0));
+
+            let (end, id) = match available.range(..(next_region, 0)).next_back() {
+                Some(free) => *free,
+                None => *available.iter().next().ok_or(section)?,
+            };
+            available.remove(&(end, id));
+            let end = end.min(region.end);
+            ranges[id] = Some(Interval::new(section, end));
+            section = end + 1;
+        }
+    }
+    Ok(ranges)
+}
+
+/// Kept elves found by giving up, whenever the split fails, the kept assignment closest before
+/// the stuck section. Quick and usually optimal, it seeds the exact search.
+fn greedy(intervals: &[Interval], covered: &IntervalSet) -> Vec<Option<Interval>> {
+    let mut kept = disjoint(intervals);
+    loop {
+        match tile(intervals, covered, &kept) {
+            Ok(ranges) => return ranges,
+            Err(section) => {
+                let index = kept
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, id)| intervals[**id].end < section)
+                    .max_by_key(|(_, id)| intervals[**id].end)
+                    .or_else(|| {
+                        kept.iter()
+                            .enumerate()
+                            .min_by_key(|(_, id)| intervals[**id].start)
+                    })
+                    .map(|(index, _)| index)
+                    .unwrap();
+                kept.remove(index);
+            }
+        }
+    }
+}
+
+/// Branch and bound over which elves keep their assignment, adding them in order of end.
+///
+/// Keeping an elf never helps a set of kept elves that could not be split already, as the elf
+/// could just as well have taken its own sections, so infeasible sets are pruned with all their
+/// extensions. The bound is the longest chain of disjoint assignments still available.
+struct Search<'a> {
+    intervals: &'a [Interval],
+    covered: IntervalSet,
+    // one elf per distinct assignment, by ascending end; identical ones cannot both be kept
+    candidates: Vec<usize>,
+    // most disjoint candidates that can be kept starting with each candidate
+    chains: Vec<usize>,
+    best: Vec<Option<Interval>>,
+    best_kept: usize,
+}
+
+impl Search<'_> {
+    fn search(&mut self, from: usize, kept: &mut Vec<usize>) {
+        for index in from..self.candidates.len() {
+            let id = self.candidates[index];
+            let interval = self.intervals[id];
+            if kept
+                .last()
+                .is_some_and(|last| self.intervals[*last].end >= interval.start)
+                || kept.len() + self.chains[index] <= self.best_kept
+            {
+                continue;
+            }
+            kept.push(id);
+            if let Ok(ranges) = tile(self.intervals, &self.covered, kept) {
+                let reassignment = Reassignment { ranges };
+                let unchanged = reassignment.unchanged(self.intervals);
+                if unchanged > self.best_kept {
+                    self.best = reassignment.ranges;
+                    self.best_kept = unchanged;
+                }
+                self.search(index + 1, kept);
+            }
+            kept.pop();
+        }
+    }
+}
+
+/// Trims assignments so no section is cleaned twice, changing as few elves as possible.
+pub fn reassign(intervals: &[Interval]) -> Reassignment {
+    let covered: IntervalSet = intervals.iter().cloned().collect();
+    let mut candidates: Vec<usize> = (0..intervals.len()).collect();
+    candidates.sort_by_key(|id| (intervals[*id].end, intervals[*id].start));
+    candidates.dedup_by_key(|id| intervals[*id]);
+
+    let mut chains = vec![1; candidates.len()];
+    for index in (0..candidates.len()).rev() {
+        let end = intervals[candidates[index]].end;
+        let longest_after = (index + 1..candidates.len())
+            .filter(|next| intervals[candidates[*next]].start > end)
+            .map(|next| chains[next])
+            .max()
+            .unwrap_or(0);
+        chains[index] += longest_after;
+    }
+
+    let best = Reassignment {
+        ranges: greedy(intervals, &covered),
+    };
+    let mut search = Search {
+        intervals,
+        covered,
+        candidates,
+        chains,
+        best_kept: best.unchanged(intervals),
+        best: best.ranges,
+    };
+    search.search(0, &mut vec![]);
+    Reassignment {
+        ranges: search.best,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::Rng;
+
+    /// Most unchanged elves over every way of giving each elf a part of its assignment or
+    /// nothing, keeping only those that clean each covered section exactly once.
+    fn brute_force(intervals: &[Interval]) -> usize {
+        fn assign(
+            intervals: &[Interval],
+            id: usize,
+            counts: &mut [u8],
+            unchanged: usize,
+        ) -> Option<usize> {
+            if id == intervals.len() {
+                return counts.iter().all(|count| *count != 1).then_some(unchanged);
+            }
+            let Interval { start, end } = intervals[id];
+            let mut best = assign(intervals, id + 1, counts, unchanged);
+            for from in start..=end {
+                for to in from..=end {
+                    let sections = from as usize..=to as usize;
+                    if sections.clone().any(|section| counts[section] == 2) {
+                        continue;
+                    }
+                    sections.clone().for_each(|section| counts[section] = 2);
+                    let kept = unchanged + (from == start && to == end) as usize;
+                    best = best.max(assign(intervals, id + 1, counts, kept));
+                    sections.for_each(|section| counts[section] = 1);
+                }
+            }
+            best
+        }
+
+        // 0 uncovered, 1 still to clean, 2 cleaned
+        let mut counts = vec![0u8; 16];
+        for interval in intervals {
+            (interval.start..=interval.end).for_each(|section| counts[section as usize] = 1);
+        }
+        assign(intervals, 0, &mut counts, 0).unwrap()
+    }
+
+    fn check(intervals: &[Interval]) -> usize {
+        let reassignment = reassign(intervals);
+        let new_ranges: Vec<Interval> = reassignment.ranges.iter().flatten().cloned().collect();
+        let covered: IntervalSet = intervals.iter().cloned().collect();
+        assert_eq!(covered, new_ranges.iter().cloned().collect::<IntervalSet>());
+        assert_eq!(
+            new_ranges.iter().map(|range| range.len()).sum::<i64>(),
+            covered.len()
+        );
+        for (new, old) in reassignment.ranges.iter().zip(intervals) {
+            assert!(new.is_none_or(|new| old.contains(&new)));
+        }
+        reassignment.unchanged(intervals)
+    }
+
+    fn intervals(ranges: &[(i64, i64)]) -> Vec<Interval> {
+        ranges
+            .iter()
+            .map(|(start, end)| Interval::new(*start, *end))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_more_than_greedy() {
+        let intervals = intervals(&[(4, 4), (3, 6), (6, 7), (7, 8), (7, 9)]);
+        assert_eq!(check(&intervals), 2);
+    }
+
+    #[test]
+    fn elf_cannot_clean_both_sides_of_a_kept_one() {
+        let intervals = intervals(&[(1, 5), (2, 2), (4, 4)]);
+        assert_eq!(check(&intervals), 1);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Rng::new(37);
+        for _ in 0..2000 {
+            let elves = 1 + rng.below(5) as usize;
+            let intervals: Vec<Interval> = (0..elves)
+                .map(|_| {
+                    let start = 1 + rng.below(8) as i64;
+                    Interval::new(start, start + rng.below(4) as i64)
+                })
+                .collect();
+            assert_eq!(
+                check(&intervals),
+                brute_force(&intervals),
+                "{:?}",
+                intervals
+            );
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::fs::File;
 use std::rc::{Rc, Weak};
 use utils::read_lines;
@@ -23,6 +23,9 @@ struct DirectoryNode {
     directories: RefCell<Vec<Rc<DirectoryNode>>>,
     files: RefCell<Vec<Rc<FileNode>>>,
     parent: Weak<DirectoryNode>,
+    // total size, `None` while it has to be recomputed; a cached directory only has cached
+    // subdirectories, so clearing a directory clears all its parents too
+    cached_size: Cell<Option<i64>>,
 }
 
 impl DirectoryNode {
@@ -32,15 +35,26 @@ impl DirectoryNode {
             directories: RefCell::new(vec![]),
             files: RefCell::new(vec![]),
             parent,
+            cached_size: Cell::new(None),
         })
     }
 
     pub fn add_file(&self, file: Rc<FileNode>) {
         self.files.borrow_mut().push(file);
+        self.invalidate_size();
     }
 
     pub fn add_directory(&self, directory: Rc<DirectoryNode>) {
         self.directories.borrow_mut().push(directory);
+        self.invalidate_size();
+    }
+
+    fn invalidate_size(&self) {
+        if self.cached_size.take().is_some() {
+            if let Some(parent) = self.parent.upgrade() {
+                parent.invalidate_size();
+            }
+        }
     }
 
     pub fn find_directory(&self, name: &str) -> Option<Rc<DirectoryNode>> {
@@ -48,20 +62,25 @@ impl DirectoryNode {
 
         directories
             .iter()
-            .map(|d| Rc::clone(d))
-            .filter(|directory| directory.name == name)
-            .next()
+            .map(Rc::clone)
+            .find(|directory| directory.name == name)
     }
 
+    /// Total size of the directory, recomputing in one post-order pass only the directories
+    /// changed since the last call.
     pub fn size(&self) -> i64 {
-        // TODO: can do caching
+        if let Some(size) = self.cached_size.get() {
+            return size;
+        }
         let directories = &*self.directories.borrow();
         let files = &*self.files.borrow();
 
         let total_file_size: i64 = files.iter().map(|file| file.size).sum();
         let total_directory_size: i64 = directories.iter().map(|directory| directory.size()).sum();
 
-        total_file_size + total_directory_size
+        let size = total_file_size + total_directory_size;
+        self.cached_size.set(Some(size));
+        size
     }
 
     pub fn find_directory_of_size_at_most(&self, size: i64) -> i64 {
@@ -99,14 +118,14 @@ struct System {
 }
 
 impl System {
-    pub fn new(commands: &mut Vec<String>) -> Self {
+    pub fn new(commands: &[String]) -> Self {
         let root = DirectoryNode::new("/".to_string(), Weak::new());
 
         let mut current = Rc::clone(&root);
 
         commands
-            .into_iter()
-            .map(|s| s.split_whitespace().into_iter())
+            .iter()
+            .map(|s| s.split_whitespace())
             .for_each(|mut command| {
                 match command.next().unwrap() {
                     "$" => match command.next().unwrap() {
@@ -146,6 +165,7 @@ impl System {
                 }
             });
 
+        root.size();
         System { root }
     }
 
@@ -168,18 +188,14 @@ impl System {
 
         sizes.sort();
 
-        sizes
-            .into_iter()
-            .filter(|s| *s >= space_to_free_up)
-            .next()
-            .unwrap()
+        sizes.into_iter().find(|s| *s >= space_to_free_up).unwrap()
     }
 }
 
 fn main() {
     let file = File::open("no_space_left_on_device/input/input.txt").unwrap();
-    let mut lines = read_lines(file);
-    let system = System::new(&mut lines);
+    let lines = read_lines(file);
+    let system = System::new(&lines);
 
     println!(
         "Part 1: total size of directory with size at most 100000 - {:?}",
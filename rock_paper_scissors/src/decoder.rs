@@ -0,0 +1,117 @@
+use std::fmt;
+
+use crate::{total_score, Shape};
+
+/// Interprets the second column of a strategy guide line as the shape to play.
+pub trait StrategyDecoder {
+    fn decode(&mut self, opponent: Shape, column: &str) -> Shape;
+}
+
+/// Any `FnMut(opponent, column) -> Shape` closure can be used as a decoder.
+impl<F: FnMut(Shape, &str) -> Shape> StrategyDecoder for F {
+    fn decode(&mut self, opponent: Shape, column: &str) -> Shape {
+        self(opponent, column)
+    }
+}
+
+/// Part 1: X/Y/Z is the shape to play.
+pub struct ShapeDecoder;
+
+impl StrategyDecoder for ShapeDecoder {
+    fn decode(&mut self, _opponent: Shape, column: &str) -> Shape {
+        Shape::new(column)
+    }
+}
+
+/// Part 2: X/Y/Z is the outcome the round has to end in.
+pub struct OutcomeDecoder;
+
+impl StrategyDecoder for OutcomeDecoder {
+    fn decode(&mut self, opponent: Shape, column: &str) -> Shape {
+        Shape::from_strategy(column, opponent)
+    }
+}
+
+/// X/Y/Z mapped to an arbitrary permutation of the three shapes.
+#[derive(Debug, Clone)]
+pub struct PermutationDecoder {
+    mapping: [Shape; 3],
+}
+
+impl PermutationDecoder {
+    pub fn new(x: Shape, y: Shape, z: Shape) -> Self {
+        PermutationDecoder { mapping: [x, y, z] }
+    }
+
+    pub fn all() -> Vec<PermutationDecoder> {
+        let mut permutations = vec![];
+        for x in Shape::ALL {
+            for y in Shape::ALL.into_iter().filter(|y| *y != x) {
+                for z in Shape::ALL.into_iter().filter(|z| *z != x && *z != y) {
+                    permutations.push(PermutationDecoder::new(x, y, z));
+                }
+            }
+        }
+        permutations
+    }
+
+    /// Tries every column to shape permutation and returns the one with the highest total score.
+    pub fn best(lines: &[String]) -> (PermutationDecoder, i64) {
+        PermutationDecoder::all()
+            .into_iter()
+            .map(|decoder| {
+                let score = total_score(lines, &mut decoder.clone());
+                (decoder, score)
+            })
+            .max_by_key(|(_, score)| *score)
+            .unwrap()
+    }
+}
+
+impl StrategyDecoder for PermutationDecoder {
+    fn decode(&mut self, _opponent: Shape, column: &str) -> Shape {
+        match column {
+            "X" => self.mapping[0],
+            "Y" => self.mapping[1],
+            "Z" => self.mapping[2],
+            _ => panic!(),
+        }
+    }
+}
+
+impl fmt::Display for PermutationDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "X={:?} Y={:?} Z={:?}",
+            self.mapping[0], self.mapping[1], self.mapping[2]
+        )
+    }
+}
+
+/// Plays whatever the opponent played in the previous round, ignoring the column.
+/// The first round has no previous move, so it is decoded by `first_round`.
+pub struct MirrorDecoder<D: StrategyDecoder> {
+    first_round: D,
+    last_opponent: Option<Shape>,
+}
+
+impl<D: StrategyDecoder> MirrorDecoder<D> {
+    pub fn new(first_round: D) -> Self {
+        MirrorDecoder {
+            first_round,
+            last_opponent: None,
+        }
+    }
+}
+
+impl<D: StrategyDecoder> StrategyDecoder for MirrorDecoder<D> {
+    fn decode(&mut self, opponent: Shape, column: &str) -> Shape {
+        let shape = match self.last_opponent {
+            Some(last) => last,
+            None => self.first_round.decode(opponent, column),
+        };
+        self.last_opponent = Some(opponent);
+        shape
+    }
+}
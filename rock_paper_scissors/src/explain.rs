@@ -0,0 +1,93 @@
+use std::fmt;
+
+use crate::decoder::StrategyDecoder;
+use crate::{Game, GameResult, Shape};
+
+pub struct RoundExplanation {
+    pub line: usize,
+    pub opponent: Shape,
+    pub player: Shape,
+    pub result: GameResult,
+    pub running_total: i64,
+}
+
+impl RoundExplanation {
+    pub fn shape_points(&self) -> i64 {
+        self.player as i64
+    }
+
+    pub fn outcome_points(&self) -> i64 {
+        self.result as i64
+    }
+}
+
+/// Every round of a strategy guide with the points it contributed.
+pub struct Explanation {
+    pub rounds: Vec<RoundExplanation>,
+}
+
+impl Explanation {
+    pub fn new(lines: &[String], decoder: &mut impl StrategyDecoder) -> Self {
+        let mut running_total = 0;
+        let rounds = lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                let game = Game::decode(line, decoder);
+                let result = game.play();
+                let (opponent, player) = (game.opponent, game.player);
+                running_total += game.score();
+                RoundExplanation {
+                    line: index + 1,
+                    opponent,
+                    player,
+                    result,
+                    running_total,
+                }
+            })
+            .collect();
+        Explanation { rounds }
+    }
+
+    pub fn total(&self) -> i64 {
+        self.rounds.last().map_or(0, |round| round.running_total)
+    }
+
+    fn count(&self, result: GameResult) -> usize {
+        self.rounds
+            .iter()
+            .filter(|round| round.result == result)
+            .count()
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>6}  {:<8} {:<8} {:<8} {:>6} {:>8} {:>8}",
+            "line", "opponent", "player", "outcome", "shape", "outcome", "total"
+        )?;
+        for round in &self.rounds {
+            writeln!(
+                f,
+                "{:>6}  {:<8} {:<8} {:<8} {:>6} {:>8} {:>8}",
+                round.line,
+                format!("{:?}", round.opponent),
+                format!("{:?}", round.player),
+                format!("{:?}", round.result),
+                round.shape_points(),
+                round.outcome_points(),
+                round.running_total
+            )?;
+        }
+        writeln!(
+            f,
+            "wins = {}, draws = {}, losses = {}, total score = {}",
+            self.count(GameResult::Victory),
+            self.count(GameResult::Draw),
+            self.count(GameResult::Loss),
+            self.total()
+        )
+    }
+}
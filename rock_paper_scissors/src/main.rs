@@ -1,14 +1,25 @@
 use std::fs::File;
 use utils::read_lines;
 
-#[derive(Copy, Clone)]
+mod decoder;
+mod explain;
+mod solver;
+mod tournament;
+
+use decoder::{MirrorDecoder, OutcomeDecoder, PermutationDecoder, ShapeDecoder, StrategyDecoder};
+use explain::Explanation;
+use tournament::{
+    Bot, FrequencyBot, GuideBot, MarkovBot, RandomBot, Tournament, WinStayLoseShiftBot,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum GameResult {
     Victory = 6,
     Draw = 3,
     Loss = 0,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum Shape {
     Rock = 1,
     Paper = 2,
@@ -16,6 +27,8 @@ enum Shape {
 }
 
 impl Shape {
+    pub const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissor];
+
     pub fn new(shape: &str) -> Self {
         match shape {
             "A" | "X" => Shape::Rock,
@@ -61,21 +74,21 @@ struct Game {
 }
 
 impl Game {
-    pub fn new(game_string: &String) -> Game {
-        let (opponent, player) = game_string.split_once(' ').unwrap();
-        return Game {
-            player: Shape::new(player),
-            opponent: Shape::new(opponent),
-        };
+    pub fn new(game_string: &str) -> Game {
+        Game::decode(game_string, &mut ShapeDecoder)
+    }
+
+    pub fn new_part_2(game_string: &str) -> Game {
+        Game::decode(game_string, &mut OutcomeDecoder)
     }
 
-    pub fn new_part_2(game_string: &String) -> Game {
-        let (opponent, strategy) = game_string.split_once(' ').unwrap();
+    pub fn decode(game_string: &str, decoder: &mut impl StrategyDecoder) -> Game {
+        let (opponent, column) = game_string.split_once(' ').unwrap();
         let opponent = Shape::new(opponent);
-        return Game {
-            player: Shape::from_strategy(strategy, opponent),
+        Game {
+            player: decoder.decode(opponent, column),
             opponent,
-        };
+        }
     }
 
     pub fn score(self) -> i64 {
@@ -103,13 +116,101 @@ impl Game {
     }
 }
 
+fn total_score(lines: &[String], decoder: &mut impl StrategyDecoder) -> i64 {
+    lines
+        .iter()
+        .map(|line| Game::decode(line, decoder).score())
+        .sum()
+}
+
 fn main() {
     let lines = read_lines(File::open("rock_paper_scissors/input/input.txt").unwrap());
+
+    let args: Vec<String> = std::env::args().collect();
+
+    match args.get(1).map(|arg| arg.as_str()) {
+        Some("permutations") => {
+            for decoder in PermutationDecoder::all() {
+                let score = total_score(&lines, &mut decoder.clone());
+                println!("{} - score = {:?}", decoder, score);
+            }
+            let (decoder, score) = PermutationDecoder::best(&lines);
+            println!("Best mapping: {} - score = {:?}", decoder, score);
+            return;
+        }
+        Some("mirror") => {
+            let score = total_score(&lines, &mut MirrorDecoder::new(ShapeDecoder));
+            println!("Mirror opponent's last move: score = {:?}", score);
+            return;
+        }
+        Some("explain") => {
+            let explanation = match args.get(2).map(|part| part.as_str()) {
+                Some("2") => Explanation::new(&lines, &mut OutcomeDecoder),
+                _ => Explanation::new(&lines, &mut ShapeDecoder),
+            };
+            print!("{}", explanation);
+            return;
+        }
+        Some("solve") => {
+            let Some(target) = args.get(2).and_then(|target| target.parse::<i64>().ok()) else {
+                println!("usage: solve <target score>");
+                std::process::exit(1);
+            };
+            let opponents: Vec<Shape> = lines
+                .iter()
+                .map(|line| Shape::new(line.split_once(' ').unwrap().0))
+                .collect();
+            match solver::solve(&opponents, target) {
+                Ok(solution) => {
+                    let guide: Vec<String> = lines
+                        .iter()
+                        .zip(solution.outcomes)
+                        .map(|(line, outcome)| format!("{} {}", &line[..1], outcome))
+                        .collect();
+                    guide.iter().for_each(|line| println!("{}", line));
+                    println!(
+                        "Target {} reached with {} wins, score = {:?}",
+                        target,
+                        solution.wins,
+                        total_score(&guide, &mut OutcomeDecoder)
+                    );
+                }
+                Err(error) => println!("Target {} is {}", target, error),
+            }
+            return;
+        }
+        Some("tournament") => {
+            let rounds = args.get(2).map_or(1000, |rounds| rounds.parse().unwrap());
+            let seed = args.get(3).map_or(2022, |seed| seed.parse().unwrap());
+            let mut bots: Vec<Box<dyn Bot>> = vec![
+                Box::new(RandomBot),
+                Box::new(FrequencyBot),
+                Box::new(MarkovBot),
+                Box::new(WinStayLoseShiftBot),
+            ];
+            match GuideBot::new(&lines) {
+                Some(guide) => bots.push(Box::new(guide)),
+                None => println!("Strategy guide is empty, playing without it"),
+            }
+            let tournament = Tournament::new(bots, rounds, seed);
+            println!("Tournament - {} rounds per match, seed {}", rounds, seed);
+            print!("{}", tournament.run());
+            return;
+        }
+        _ => {}
+    }
+
     let score: i64 = lines.iter().map(|line| Game::new(line).score()).sum();
-    println!("Part 1: highest calories in elves = {:?}", score);
+    println!(
+        "Part 1: total score following the strategy guide = {:?}",
+        score
+    );
     let score: i64 = lines
         .iter()
         .map(|line| Game::new_part_2(line).score())
         .sum();
-    println!("Part 1: highest calories in elves = {:?}", score);
+    println!(
+        "Part 2: total score following the strategy guide outcomes = {:?}",
+        score
+    );
 }
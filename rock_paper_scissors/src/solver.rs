@@ -0,0 +1,168 @@
+use std::fmt;
+
+use crate::{Game, Shape};
+
+const OUTCOMES: [&str; 3] = ["X", "Y", "Z"];
+const UNREACHABLE: u32 = u32::MAX;
+/// Largest table of per-round choices `solve` is willing to allocate, in bytes.
+const MAX_CHOICES: usize = 1 << 30;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// Outside `rounds..=9 * rounds`, the lowest and highest possible totals.
+    OutOfRange,
+    /// In range, but no outcome column adds up to it.
+    Unreachable,
+    /// Remembering every round's choice would take more than `MAX_CHOICES` bytes.
+    TooLarge { bytes: usize },
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::OutOfRange => write!(f, "out of range for this many rounds"),
+            SolveError::Unreachable => write!(f, "unreachable"),
+            SolveError::TooLarge { bytes } => write!(
+                f,
+                "too large to solve, it would need {} MiB of choices",
+                bytes >> 20
+            ),
+        }
+    }
+}
+
+/// An outcome column that reaches the requested total score.
+pub struct Solution {
+    pub outcomes: Vec<&'static str>,
+    pub wins: usize,
+}
+
+fn round_score(opponent: Shape, outcome: &str) -> usize {
+    Game {
+        player: Shape::from_strategy(outcome, opponent),
+        opponent,
+    }
+    .score() as usize
+}
+
+/// Picks an outcome for every round so the total score is exactly `target`, using as few wins
+/// as possible.
+pub fn solve(opponents: &[Shape], target: i64) -> Result<Solution, SolveError> {
+    // every round scores between 1 (losing with rock) and 9 (winning with scissors)
+    let rounds = opponents.len() as i64;
+    if target < rounds || target > 9 * rounds {
+        return Err(SolveError::OutOfRange);
+    }
+    let target = target as usize;
+    let bytes = opponents.len().saturating_mul(target + 1);
+    if bytes > MAX_CHOICES {
+        return Err(SolveError::TooLarge { bytes });
+    }
+
+    // wins[t] = fewest wins needed to score exactly t in the rounds seen so far
+    let mut wins = vec![UNREACHABLE; target + 1];
+    wins[0] = 0;
+    // choices[round * (target + 1) + t] = outcome index that reached t in that round
+    let mut choices = vec![0u8; opponents.len() * (target + 1)];
+
+    for (round, opponent) in opponents.iter().enumerate() {
+        let mut next = vec![UNREACHABLE; target + 1];
+        for (total, current) in wins.iter().enumerate() {
+            if *current == UNREACHABLE {
+                continue;
+            }
+            for (index, outcome) in OUTCOMES.iter().enumerate() {
+                let reached = total + round_score(*opponent, outcome);
+                if reached > target {
+                    continue;
+                }
+                let candidate = current + (*outcome == "Z") as u32;
+                if candidate < next[reached] {
+                    next[reached] = candidate;
+                    choices[round * (target + 1) + reached] = index as u8;
+                }
+            }
+        }
+        wins = next;
+    }
+
+    if wins[target] == UNREACHABLE {
+        return Err(SolveError::Unreachable);
+    }
+
+    let mut outcomes = vec![""; opponents.len()];
+    let mut total = target;
+    for round in (0..opponents.len()).rev() {
+        let outcome = OUTCOMES[choices[round * (target + 1) + total] as usize];
+        outcomes[round] = outcome;
+        total -= round_score(opponents[round], outcome);
+    }
+
+    Ok(Solution {
+        outcomes,
+        wins: wins[target] as usize,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decoder::OutcomeDecoder;
+    use crate::total_score;
+    use utils::Rng;
+
+    #[test]
+    fn rejects_targets_out_of_range_without_allocating() {
+        let opponents = vec![Shape::Rock; 3];
+        assert_eq!(solve(&opponents, 2).err(), Some(SolveError::OutOfRange));
+        assert_eq!(solve(&opponents, 28).err(), Some(SolveError::OutOfRange));
+        assert_eq!(
+            solve(&opponents, 100_000_000_000).err(),
+            Some(SolveError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn reaches_both_extremes() {
+        let opponents = vec![Shape::Paper; 3];
+        assert_eq!(solve(&opponents, 3).unwrap().wins, 0);
+        assert_eq!(solve(&opponents, 27).unwrap().wins, 3);
+    }
+
+    #[test]
+    fn tells_unreachable_from_too_large() {
+        // against rock every round scores 3, 4 or 8
+        assert_eq!(
+            solve(&[Shape::Rock], 5).err(),
+            Some(SolveError::Unreachable)
+        );
+        // 70000 rounds used to be refused outright, now only the table size limits them
+        let opponents = vec![Shape::Rock; 70_000];
+        assert_eq!(
+            solve(&opponents, 4 * 70_000).err(),
+            Some(SolveError::TooLarge {
+                bytes: 70_000 * (4 * 70_000 + 1)
+            })
+        );
+    }
+
+    #[test]
+    fn outcomes_replay_to_the_target() {
+        let mut rng = Rng::new(29);
+        let opponents: Vec<Shape> = (0..200)
+            .map(|_| Shape::ALL[rng.below(3) as usize])
+            .collect();
+        for target in [700, 777, 1000, 1234, 1500] {
+            let solution = solve(&opponents, target).unwrap();
+            let guide: Vec<String> = opponents
+                .iter()
+                .zip(&solution.outcomes)
+                .map(|(opponent, outcome)| {
+                    let column = ["A", "B", "C"][*opponent as usize - 1];
+                    format!("{} {}", column, outcome)
+                })
+                .collect();
+            assert_eq!(total_score(&guide, &mut OutcomeDecoder), target);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::fmt;
+use utils::Rng;
+
+use crate::decoder::ShapeDecoder;
+use crate::{Game, GameResult, Shape};
+
+fn random_shape(rng: &mut Rng) -> Shape {
+    Shape::ALL[rng.below(3) as usize]
+}
+
+/// One round as seen by a bot: its own shape and the shape its opponent played.
+#[derive(Debug, Copy, Clone)]
+pub struct Round {
+    pub own: Shape,
+    pub opponent: Shape,
+}
+
+impl Round {
+    fn game(&self) -> Game {
+        Game {
+            player: self.own,
+            opponent: self.opponent,
+        }
+    }
+
+    pub fn result(&self) -> GameResult {
+        self.game().play()
+    }
+
+    pub fn score(&self) -> i64 {
+        self.game().score()
+    }
+}
+
+pub trait Bot {
+    fn name(&self) -> String;
+
+    /// Picks the next shape given every previous round of the current match.
+    fn play(&self, history: &[Round], rng: &mut Rng) -> Shape;
+}
+
+pub struct RandomBot;
+
+impl Bot for RandomBot {
+    fn name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn play(&self, _history: &[Round], rng: &mut Rng) -> Shape {
+        random_shape(rng)
+    }
+}
+
+fn index(shape: Shape) -> usize {
+    shape as usize - 1
+}
+
+/// Most common entry of `counts`, or a random shape when nothing has been counted yet.
+fn most_common(counts: &[usize; 3], rng: &mut Rng) -> Shape {
+    let max = *counts.iter().max().unwrap();
+    if max == 0 {
+        return random_shape(rng);
+    }
+    let candidates: Vec<Shape> = Shape::ALL
+        .into_iter()
+        .filter(|shape| counts[index(*shape)] == max)
+        .collect();
+    candidates[rng.below(candidates.len() as u64) as usize]
+}
+
+/// Counters the shape the opponent has played most often.
+pub struct FrequencyBot;
+
+impl Bot for FrequencyBot {
+    fn name(&self) -> String {
+        "frequency".to_string()
+    }
+
+    fn play(&self, history: &[Round], rng: &mut Rng) -> Shape {
+        let mut counts = [0; 3];
+        history
+            .iter()
+            .for_each(|round| counts[index(round.opponent)] += 1);
+        Shape::win(most_common(&counts, rng))
+    }
+}
+
+/// Counters the opponent's most likely next shape, given the shape they played last.
+pub struct MarkovBot;
+
+impl Bot for MarkovBot {
+    fn name(&self) -> String {
+        "markov".to_string()
+    }
+
+    fn play(&self, history: &[Round], rng: &mut Rng) -> Shape {
+        let last = match history.last() {
+            Some(round) => round.opponent,
+            None => return random_shape(rng),
+        };
+        let mut transitions = [[0; 3]; 3];
+        history
+            .windows(2)
+            .for_each(|w| transitions[index(w[0].opponent)][index(w[1].opponent)] += 1);
+        Shape::win(most_common(&transitions[index(last)], rng))
+    }
+}
+
+/// Repeats a winning shape, otherwise moves on to the shape that beats the one just played.
+pub struct WinStayLoseShiftBot;
+
+impl Bot for WinStayLoseShiftBot {
+    fn name(&self) -> String {
+        "win-stay/lose-shift".to_string()
+    }
+
+    fn play(&self, history: &[Round], rng: &mut Rng) -> Shape {
+        match history.last() {
+            None => random_shape(rng),
+            Some(round) => match round.result() {
+                GameResult::Victory => round.own,
+                GameResult::Draw | GameResult::Loss => Shape::win(round.own),
+            },
+        }
+    }
+}
+
+/// Replays the player column of a strategy guide, starting over once it runs out.
+pub struct GuideBot {
+    shapes: Vec<Shape>,
+}
+
+impl GuideBot {
+    /// None for an empty guide, which has nothing to play.
+    pub fn new(lines: &[String]) -> Option<Self> {
+        let shapes: Vec<Shape> = lines
+            .iter()
+            .map(|line| Game::decode(line, &mut ShapeDecoder).player)
+            .collect();
+        (!shapes.is_empty()).then_some(GuideBot { shapes })
+    }
+}
+
+impl Bot for GuideBot {
+    fn name(&self) -> String {
+        "strategy-guide".to_string()
+    }
+
+    fn play(&self, history: &[Round], _rng: &mut Rng) -> Shape {
+        self.shapes[history.len() % self.shapes.len()]
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Standing {
+    pub name: String,
+    pub score: i64,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Standing {
+    fn record(&mut self, round: &Round) {
+        self.score += round.score();
+        match round.result() {
+            GameResult::Victory => self.wins += 1,
+            GameResult::Draw => self.draws += 1,
+            GameResult::Loss => self.losses += 1,
+        }
+    }
+}
+
+/// League table, highest total score first.
+pub struct LeagueTable {
+    pub standings: Vec<Standing>,
+}
+
+impl fmt::Display for LeagueTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>4}  {:<20} {:>8} {:>6} {:>6} {:>6}",
+            "#", "bot", "score", "wins", "draws", "losses"
+        )?;
+        for (rank, s) in self.standings.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:<20} {:>8} {:>6} {:>6} {:>6}",
+                rank + 1,
+                s.name,
+                s.score,
+                s.wins,
+                s.draws,
+                s.losses
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Round robin: every bot plays every other bot for `rounds` rounds.
+pub struct Tournament {
+    bots: Vec<Box<dyn Bot>>,
+    rounds: usize,
+    rng: Rng,
+}
+
+impl Tournament {
+    pub fn new(bots: Vec<Box<dyn Bot>>, rounds: usize, seed: u64) -> Self {
+        Tournament {
+            bots,
+            rounds,
+            rng: Rng::new(seed),
+        }
+    }
+
+    pub fn run(mut self) -> LeagueTable {
+        let mut standings: Vec<Standing> = self
+            .bots
+            .iter()
+            .map(|bot| Standing {
+                name: bot.name(),
+                ..Default::default()
+            })
+            .collect();
+
+        for first in 0..self.bots.len() {
+            for second in (first + 1)..self.bots.len() {
+                let mut first_history: Vec<Round> = Vec::with_capacity(self.rounds);
+                let mut second_history: Vec<Round> = Vec::with_capacity(self.rounds);
+
+                for _ in 0..self.rounds {
+                    let first_shape = self.bots[first].play(&first_history, &mut self.rng);
+                    let second_shape = self.bots[second].play(&second_history, &mut self.rng);
+
+                    let first_round = Round {
+                        own: first_shape,
+                        opponent: second_shape,
+                    };
+                    let second_round = Round {
+                        own: second_shape,
+                        opponent: first_shape,
+                    };
+
+                    standings[first].record(&first_round);
+                    standings[second].record(&second_round);
+                    first_history.push(first_round);
+                    second_history.push(second_round);
+                }
+            }
+        }
+
+        standings.sort_by_key(|standing| std::cmp::Reverse(standing.score));
+        LeagueTable { standings }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bots() -> Vec<Box<dyn Bot>> {
+        let guide = ["A Y", "B X", "C Z"].map(String::from);
+        vec![
+            Box::new(RandomBot),
+            Box::new(FrequencyBot),
+            Box::new(MarkovBot),
+            Box::new(WinStayLoseShiftBot),
+            Box::new(GuideBot::new(&guide).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_league_table() {
+        let table = Tournament::new(bots(), 500, 7).run().to_string();
+        assert_eq!(Tournament::new(bots(), 500, 7).run().to_string(), table);
+        assert_ne!(Tournament::new(bots(), 500, 8).run().to_string(), table);
+    }
+
+    #[test]
+    fn empty_guide_has_no_bot() {
+        assert!(GuideBot::new(&[]).is_none());
+    }
+}
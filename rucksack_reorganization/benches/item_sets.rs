@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rucksack_reorganization::item_set::ItemSet;
+use rucksack_reorganization::priority;
+use utils::Rng;
+
+const RUCKSACKS: usize = 1_000_000;
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn generate(rucksacks: usize, rng: &mut Rng) -> Vec<String> {
+    (0..rucksacks)
+        .map(|_| {
+            let length = 2 * (8 + rng.below(24) as usize);
+            (0..length)
+                .map(|_| ITEMS[rng.below(ITEMS.len() as u64) as usize] as char)
+                .collect()
+        })
+        .collect()
+}
+
+// Both sides sum every shared item rather than assuming exactly one, since generated
+// rucksacks are not guaranteed to be well formed.
+
+fn compartments_hash_set(lines: &[String]) -> i64 {
+    lines
+        .iter()
+        .map(|line| {
+            let (left, right) = line.split_at(line.len() / 2);
+            let left: HashSet<char> = left.chars().collect();
+            let right: HashSet<char> = right.chars().collect();
+            left.intersection(&right)
+                .map(|item| priority(*item))
+                .sum::<i64>()
+        })
+        .sum()
+}
+
+fn compartments_item_set(lines: &[String]) -> i64 {
+    lines
+        .iter()
+        .map(|line| {
+            let (left, right) = line.split_at(line.len() / 2);
+            let (left, right) = (ItemSet::try_from(left), ItemSet::try_from(right));
+            left.unwrap().intersection(&right.unwrap()).priority_sum()
+        })
+        .sum()
+}
+
+fn groups_hash_set(lines: &[String]) -> i64 {
+    lines
+        .chunks(3)
+        .map(|chunks| {
+            chunks
+                .iter()
+                .map(|chunk| chunk.chars().collect::<HashSet<char>>())
+                .reduce(|acc, element| acc.intersection(&element).cloned().collect())
+                .unwrap()
+                .into_iter()
+                .map(priority)
+                .sum::<i64>()
+        })
+        .sum()
+}
+
+fn groups_item_set(lines: &[String]) -> i64 {
+    lines
+        .chunks(3)
+        .map(|chunks| {
+            chunks
+                .iter()
+                .map(|chunk| ItemSet::try_from(chunk.as_str()).unwrap())
+                .reduce(|acc, element| acc.intersection(&element))
+                .unwrap()
+                .priority_sum()
+        })
+        .sum()
+}
+
+/// The `HashSet<char>` and `ItemSet` versions of both parts on generated rucksacks.
+fn item_sets(c: &mut Criterion) {
+    let lines = generate(RUCKSACKS, &mut Rng::new(2022));
+
+    let mut group = c.benchmark_group("item sets");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(RUCKSACKS as u64));
+    group.bench_function("part 1 HashSet<char>", |b| {
+        b.iter(|| compartments_hash_set(&lines))
+    });
+    group.bench_function("part 1 ItemSet", |b| {
+        b.iter(|| compartments_item_set(&lines))
+    });
+    group.bench_function("part 2 HashSet<char>", |b| {
+        b.iter(|| groups_hash_set(&lines))
+    });
+    group.bench_function("part 2 ItemSet", |b| b.iter(|| groups_item_set(&lines)));
+    group.finish();
+}
+
+criterion_group!(benches, item_sets);
+criterion_main!(benches);
@@ -0,0 +1,200 @@
+use crate::item_set::ItemSet;
+
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub members: Vec<usize>,
+    pub badge: char,
+}
+
+/// Every combination of `size` rucksacks that shares exactly one item type.
+fn candidate_groups(rucksacks: &[ItemSet], size: usize) -> Vec<Group> {
+    fn extend(
+        rucksacks: &[ItemSet],
+        size: usize,
+        members: &mut Vec<usize>,
+        shared: ItemSet,
+        groups: &mut Vec<Group>,
+    ) {
+        if members.len() == size {
+            if shared.len() == 1 {
+                groups.push(Group {
+                    members: members.clone(),
+                    badge: shared.iter().next().unwrap(),
+                });
+            }
+            return;
+        }
+        let start = members.last().map_or(0, |last| last + 1);
+        for candidate in start..rucksacks.len() {
+            let next = shared.intersection(&rucksacks[candidate]);
+            if next.is_empty() {
+                continue;
+            }
+            members.push(candidate);
+            extend(rucksacks, size, members, next, groups);
+            members.pop();
+        }
+    }
+
+    let mut groups = vec![];
+    extend(rucksacks, size, &mut vec![], ItemSet::all(), &mut groups);
+    groups
+}
+
+/// Exact cover search over the candidate groups (Knuth's Algorithm X), always branching on the
+/// uncovered rucksack with the fewest candidate groups left.
+struct Search {
+    groups: Vec<Group>,
+    groups_of: Vec<Vec<usize>>,
+    active: Vec<bool>,
+    options: Vec<usize>,
+    covered: Vec<bool>,
+    chosen: Vec<usize>,
+}
+
+impl Search {
+    fn new(groups: Vec<Group>, rucksacks: usize) -> Self {
+        let mut groups_of = vec![vec![]; rucksacks];
+        for (id, group) in groups.iter().enumerate() {
+            group
+                .members
+                .iter()
+                .for_each(|member| groups_of[*member].push(id));
+        }
+        Search {
+            active: vec![true; groups.len()],
+            options: groups_of.iter().map(|ids| ids.len()).collect(),
+            covered: vec![false; rucksacks],
+            chosen: vec![],
+            groups,
+            groups_of,
+        }
+    }
+
+    fn select(&mut self, id: usize) -> Vec<usize> {
+        let mut removed = vec![];
+        for member in self.groups[id].members.clone() {
+            self.covered[member] = true;
+            for other in self.groups_of[member].clone() {
+                if self.active[other] {
+                    self.active[other] = false;
+                    self.groups[other]
+                        .members
+                        .iter()
+                        .for_each(|m| self.options[*m] -= 1);
+                    removed.push(other);
+                }
+            }
+        }
+        self.chosen.push(id);
+        removed
+    }
+
+    fn deselect(&mut self, id: usize, removed: Vec<usize>) {
+        self.chosen.pop();
+        for other in removed.into_iter().rev() {
+            self.active[other] = true;
+            self.groups[other]
+                .members
+                .iter()
+                .for_each(|m| self.options[*m] += 1);
+        }
+        for member in self.groups[id].members.clone() {
+            self.covered[member] = false;
+        }
+    }
+
+    fn solve(&mut self) -> bool {
+        let rucksack = (0..self.covered.len())
+            .filter(|r| !self.covered[*r])
+            .min_by_key(|r| self.options[*r]);
+        let rucksack = match rucksack {
+            Some(rucksack) => rucksack,
+            None => return true,
+        };
+
+        for index in 0..self.groups_of[rucksack].len() {
+            let id = self.groups_of[rucksack][index];
+            if !self.active[id] {
+                continue;
+            }
+            let removed = self.select(id);
+            if self.solve() {
+                return true;
+            }
+            self.deselect(id, removed);
+        }
+        false
+    }
+}
+
+/// Splits all rucksacks into groups of `size` where every group shares exactly one item type.
+/// The search is exhaustive, so `None` means no such partition exists.
+pub fn discover_groups(rucksacks: &[ItemSet], size: usize) -> Option<Vec<Group>> {
+    if size == 0 || !rucksacks.len().is_multiple_of(size) {
+        return None;
+    }
+    let mut search = Search::new(candidate_groups(rucksacks, size), rucksacks.len());
+    if !search.solve() {
+        return None;
+    }
+    let mut groups: Vec<Group> = search
+        .chosen
+        .iter()
+        .map(|id| search.groups[*id].clone())
+        .collect();
+    groups.sort_by_key(|group| group.members[0]);
+    Some(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rucksacks(lines: &[&str]) -> Vec<ItemSet> {
+        lines
+            .iter()
+            .map(|line| ItemSet::try_from(*line).unwrap())
+            .collect()
+    }
+
+    /// The puzzle example with its two groups interleaved.
+    fn shuffled_example() -> Vec<ItemSet> {
+        rucksacks(&[
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "ttgJtRGJQctTZtZT",
+            "PmmdzqPrVvPwwTWBwg",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ])
+    }
+
+    #[test]
+    fn finds_the_groups_of_a_shuffled_list() {
+        let groups = discover_groups(&shuffled_example(), 3).unwrap();
+        let found: Vec<(Vec<usize>, char)> = groups
+            .into_iter()
+            .map(|group| (group.members, group.badge))
+            .collect();
+        assert_eq!(found, vec![(vec![0, 2, 4], 'r'), (vec![1, 3, 5], 'Z')]);
+    }
+
+    #[test]
+    fn reports_when_no_partition_exists() {
+        // every pair shares either two item types or none
+        assert!(discover_groups(&rucksacks(&["ab", "ab", "cd", "cd"]), 2).is_none());
+        // only ab, ac and ad share an item, which leaves b, c and d sharing nothing
+        assert!(discover_groups(&rucksacks(&["ab", "ac", "ad", "b", "c", "d"]), 3).is_none());
+    }
+
+    #[test]
+    fn size_has_to_divide_the_number_of_rucksacks() {
+        assert!(discover_groups(&shuffled_example(), 4).is_none());
+        assert!(discover_groups(&shuffled_example(), 0).is_none());
+        assert_eq!(
+            discover_groups(&shuffled_example(), 6).map(|g| g.len()),
+            None
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use crate::priority;
+use crate::validation::RucksackError;
+
+/// Set of item types stored as a bitmask, bit `n` standing for the item of priority `n`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + (priority - 1) as u8) as char,
+        27..=52 => (b'A' + (priority - 27) as u8) as char,
+        _ => panic!("no item has priority {}", priority),
+    }
+}
+
+impl ItemSet {
+    pub fn new() -> Self {
+        ItemSet(0)
+    }
+
+    /// Every item type, `a` to `Z`.
+    pub fn all() -> Self {
+        ItemSet(((1 << 52) - 1) << 1)
+    }
+
+    /// Adds `item`, which has to be a letter.
+    pub fn insert(&mut self, item: char) {
+        if !item.is_ascii_alphabetic() {
+            panic!("invalid item {:?}", item);
+        }
+        self.0 |= 1 << priority(item);
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        item.is_ascii_alphabetic() && self.0 & (1 << priority(item)) != 0
+    }
+
+    pub fn union(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn difference(&self, other: &ItemSet) -> ItemSet {
+        ItemSet(self.0 & !other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Items in ascending priority order.
+    pub fn iter(&self) -> impl Iterator<Item = char> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let priority = bits.trailing_zeros();
+            bits &= bits - 1;
+            Some(item(priority))
+        })
+    }
+
+    pub fn priority_sum(&self) -> i64 {
+        self.iter().map(priority).sum()
+    }
+}
+
+/// The item types of a rucksack, or the first character that is not an item.
+impl TryFrom<&str> for ItemSet {
+    type Error = RucksackError;
+
+    fn try_from(items: &str) -> Result<Self, RucksackError> {
+        let mut set = ItemSet::new();
+        for (position, item) in items.chars().enumerate() {
+            if !item.is_ascii_alphabetic() {
+                return Err(RucksackError::InvalidCharacter { item, position });
+            }
+            set.insert(item);
+        }
+        Ok(set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use utils::Rng;
+
+    fn items(set: ItemSet) -> String {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn set_operations() {
+        let left = ItemSet::try_from("vJrwpWtwJgWr").unwrap();
+        let right = ItemSet::try_from("hcsFMMfFFhFp").unwrap();
+        assert_eq!(items(left.union(&right)), "cfghprstvwFJMW");
+        assert_eq!(items(left.intersection(&right)), "p");
+        assert_eq!(items(left.difference(&right)), "grtvwJW");
+        assert_eq!(items(right.difference(&left)), "cfhsFM");
+        assert!(left.difference(&left).is_empty());
+    }
+
+    #[test]
+    fn iterates_in_priority_order() {
+        let set = ItemSet::try_from("ZzAa").unwrap();
+        assert_eq!(items(set), "azAZ");
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.priority_sum(), 1 + 26 + 27 + 52);
+        assert!(set.contains('A') && !set.contains('b') && !set.contains('1'));
+        assert!(ItemSet::new().is_empty());
+    }
+
+    #[test]
+    fn rejects_characters_that_are_not_items() {
+        assert_eq!(
+            ItemSet::try_from("PmmdzqPrV1vPwwTWBwg"),
+            Err(RucksackError::InvalidCharacter {
+                item: '1',
+                position: 9
+            })
+        );
+        assert_eq!(ItemSet::all().len(), 52);
+        assert_eq!(ItemSet::all().iter().last(), Some('Z'));
+    }
+
+    #[test]
+    fn agrees_with_hash_set_on_generated_rucksacks() {
+        let mut rng = Rng::new(31);
+        let letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        for _ in 0..1000 {
+            let [left, right]: [String; 2] = [0, 1].map(|_| {
+                (0..rng.below(40))
+                    .map(|_| letters[rng.below(52) as usize])
+                    .collect()
+            });
+            let (left_set, right_set) = (
+                ItemSet::try_from(left.as_str()).unwrap(),
+                ItemSet::try_from(right.as_str()).unwrap(),
+            );
+            let (left, right): (HashSet<char>, HashSet<char>) =
+                (left.chars().collect(), right.chars().collect());
+            let mut shared: Vec<char> = left.intersection(&right).copied().collect();
+            shared.sort_by_key(|item| priority(*item));
+            assert_eq!(
+                left_set
+                    .intersection(&right_set)
+                    .iter()
+                    .collect::<Vec<char>>(),
+                shared
+            );
+            assert_eq!(left_set.union(&right_set).len(), left.union(&right).count());
+            assert_eq!(
+                left_set.difference(&right_set).len(),
+                left.difference(&right).count()
+            );
+        }
+    }
+}
@@ -0,0 +1,12 @@
+pub mod groups;
+pub mod item_set;
+pub mod repack;
+pub mod validation;
+
+pub fn priority(item: char) -> i64 {
+    if item <= 'Z' {
+        item as i64 - 'A' as i64 + 27
+    } else {
+        item as i64 - 'a' as i64 + 1
+    }
+}
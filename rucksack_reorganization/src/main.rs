@@ -1,44 +1,158 @@
-use std::collections::HashSet;
 use std::fs::File;
 use utils::read_lines;
 
-fn priority(item: char) -> i64 {
-    if item <= 'Z' {
-        item as i64 - 'A' as i64 + 27
-    } else {
-        item as i64 - 'a' as i64 + 1
+use rucksack_reorganization::item_set::ItemSet;
+use rucksack_reorganization::validation::{validate, RucksackError, ValidationReport};
+use rucksack_reorganization::{groups, priority, repack};
+
+/// Sum of the badge priorities of every group of three lines, and how many groups were left
+/// out because a line holds something other than items or the group shares no single item.
+fn badges_total(lines: &[String]) -> (i64, usize) {
+    let (mut total, mut skipped) = (0, 0);
+    for group in lines.chunks(3) {
+        let shared = group
+            .iter()
+            .map(|line| ItemSet::try_from(line.as_str()))
+            .reduce(|acc, element| Ok(acc?.intersection(&element?)));
+        match shared {
+            Some(Ok(shared)) if group.len() == 3 && shared.len() == 1 => {
+                total += shared.priority_sum()
+            }
+            _ => skipped += 1,
+        }
     }
+    (total, skipped)
+}
+
+fn read_input(path: Option<&String>) -> Vec<String> {
+    let path = path.map_or("./rucksack_reorganization/input/input.txt", |path| path);
+    read_lines(File::open(path).unwrap())
 }
 
 fn main() {
-    let file = File::open("./rucksack_reorganization/input/input.txt").unwrap();
-    let lines = read_lines(file);
-
-    let common_chars = lines.iter().map(|line| {
-        let (left, right) = line.split_at(line.len() / 2);
-        let left: HashSet<char> = left.chars().collect();
-        let right: HashSet<char> = right.chars().collect();
-        let common_char = left.intersection(&right).next().unwrap();
-        common_char.clone()
-    });
-
-    let total: i64 = common_chars.map(|item| priority(item)).sum();
-    println!("Part 1: sum of the priorities of common items {:?}", total);
+    let args: Vec<String> = std::env::args().collect();
+    let mode = args.get(1).map(|arg| arg.as_str());
 
-    let badges = lines.chunks(3).map(|chunks| {
-        let common_char = chunks
-            .iter()
-            .map(|chunk| chunk.chars().collect::<HashSet<char>>())
-            .reduce(|acc, element| acc.intersection(&element).cloned().collect())
-            .unwrap();
+    if let Some("groups") = mode {
+        let size = args.get(2).map_or(3, |size| size.parse().unwrap());
+        let lines = read_input(args.get(3));
+        // line numbers of the rucksacks taking part, so skipped lines keep their numbers
+        let (mut line_numbers, mut rucksacks) = (vec![], vec![]);
+        for (index, line) in lines.iter().enumerate() {
+            match ItemSet::try_from(line.as_str()) {
+                Ok(rucksack) => {
+                    line_numbers.push(index + 1);
+                    rucksacks.push(rucksack);
+                }
+                Err(error) => println!("line {}: skipped, {}", index + 1, error),
+            }
+        }
+        match groups::discover_groups(&rucksacks, size) {
+            Some(groups) => {
+                for group in &groups {
+                    let members: Vec<String> = group
+                        .members
+                        .iter()
+                        .map(|m| line_numbers[*m].to_string())
+                        .collect();
+                    println!("lines {} - badge {}", members.join(", "), group.badge);
+                }
+                let total: i64 = groups.iter().map(|group| priority(group.badge)).sum();
+                println!("Sum of the priorities of discovered badges {:?}", total);
+            }
+            None => println!(
+                "No partition into groups of {} with a single badge exists",
+                size
+            ),
+        }
+        return;
+    }
 
-        let common_char = common_char.iter().next().unwrap();
-        return common_char.clone();
-    });
+    let lines = read_input(args.get(2));
 
-    let badges_total: i64 = badges.map(|item| priority(item)).sum();
+    if let Some("repack") = mode {
+        let (mut total_moves, mut repacked, mut unbalanced) = (0, 0, 0);
+        for (index, line) in lines.iter().enumerate() {
+            if let Err(
+                error @ (RucksackError::OddLength(_) | RucksackError::InvalidCharacter { .. }),
+            ) = validate(line)
+            {
+                println!("line {}: skipped, {}", index + 1, error);
+                continue;
+            }
+            match repack::repack(line) {
+                Some(repack) if repack.total_moves() > 0 => {
+                    let moves: Vec<String> = repack.moves.iter().map(|m| m.to_string()).collect();
+                    println!(
+                        "line {}: {} -> {} ({} moves: {})",
+                        index + 1,
+                        line,
+                        repack.corrected,
+                        repack.total_moves(),
+                        moves.join(", ")
+                    );
+                    total_moves += repack.total_moves();
+                    repacked += 1;
+                }
+                Some(_) => {}
+                None => {
+                    println!("line {}: compartments cannot be balanced", index + 1);
+                    unbalanced += 1;
+                }
+            }
+        }
+        println!(
+            "Total moves: {} across {} rucksacks, {} rucksacks cannot be balanced",
+            total_moves, repacked, unbalanced
+        );
+        return;
+    }
+
+    let report = ValidationReport::new(&lines);
+    if let Some("validate") = mode {
+        print!("{}", report);
+        return;
+    }
+    if !report.errors.is_empty() {
+        println!(
+            "Skipping {} invalid rucksacks, run with `validate` for details",
+            report.errors.len()
+        );
+    }
+
+    let total: i64 = report.valid.iter().map(|(_, item)| priority(*item)).sum();
+    println!("Part 1: sum of the priorities of common items {:?}", total);
+
+    let (badges_total, skipped) = badges_total(&lines);
+    if skipped > 0 {
+        println!(
+            "Skipping {} groups of three without a single shared item",
+            skipped
+        );
+    }
     println!(
         "Part 2: sum of the priorities of common items {:?}",
         badges_total
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn badges_skip_groups_with_invalid_lines() {
+        let lines: Vec<String> = [
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGz1wwsLwLmpwMDw",
+        ]
+        .map(String::from)
+        .to_vec();
+        assert_eq!(badges_total(&lines), (18, 1));
+        assert_eq!(badges_total(&lines[..3]), (18, 0));
+    }
+}
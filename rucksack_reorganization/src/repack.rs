@@ -0,0 +1,153 @@
+use std::fmt;
+
+use crate::item_set::ItemSet;
+use crate::priority;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    LeftToRight,
+    RightToLeft,
+}
+
+/// `count` copies of `item` moved to the other compartment.
+#[derive(Debug)]
+pub struct ItemMove {
+    pub item: char,
+    pub count: usize,
+    pub direction: Direction,
+}
+
+impl fmt::Display for ItemMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::LeftToRight => "left -> right",
+            Direction::RightToLeft => "right -> left",
+        };
+        write!(f, "{} x{} {}", self.item, self.count, direction)
+    }
+}
+
+#[derive(Debug)]
+pub struct Repack {
+    pub corrected: String,
+    pub moves: Vec<ItemMove>,
+}
+
+impl Repack {
+    pub fn total_moves(&self) -> usize {
+        self.moves.iter().map(|m| m.count).sum()
+    }
+}
+
+fn counts(compartment: &str) -> [usize; 53] {
+    let mut counts = [0; 53];
+    compartment
+        .chars()
+        .for_each(|item| counts[priority(item) as usize] += 1);
+    counts
+}
+
+/// Finds the fewest single-item moves between compartments after which no item type is in both.
+///
+/// Every copy of a type has to end up on the same side, and both compartments have to keep
+/// the same number of items, so each type is either gathered left or gathered right and the
+/// choice is a small knapsack over compartment size. Returns `None` when no choice keeps the
+/// compartments balanced. Items have to be letters, as checked by `validate`.
+pub fn repack(line: &str) -> Option<Repack> {
+    let half = line.len() / 2;
+    let (left, right) = line.split_at(half);
+    let (left_counts, right_counts) = (counts(left), counts(right));
+    let items = |half| ItemSet::try_from(half).expect("rucksack holds only items");
+    let all_types = items(left).union(&items(right));
+    let types: Vec<char> = all_types.iter().collect();
+    let count = |item: char| {
+        let p = priority(item) as usize;
+        (left_counts[p], right_counts[p])
+    };
+
+    // cost[i][s] = fewest moves placing the first i types with s items on the left
+    let mut cost = vec![vec![None; line.len() + 1]; types.len() + 1];
+    cost[0][0] = Some(0);
+    for (i, item) in types.iter().enumerate() {
+        let (l, r) = count(*item);
+        for size in 0..=line.len() {
+            let Some(current) = cost[i][size] else {
+                continue;
+            };
+            let stay_left = &mut cost[i + 1][size + l + r];
+            *stay_left = Some(stay_left.map_or(current + r, |c: usize| c.min(current + r)));
+            let go_right = &mut cost[i + 1][size];
+            *go_right = Some(go_right.map_or(current + l, |c: usize| c.min(current + l)));
+        }
+    }
+    cost[types.len()][half]?;
+
+    let mut gathered_left = ItemSet::new();
+    let mut size = half;
+    for (i, item) in types.iter().enumerate().rev() {
+        let (l, r) = count(*item);
+        let target = cost[i + 1][size].unwrap();
+        if size >= l + r && cost[i][size - l - r].map(|c| c + r) == Some(target) {
+            gathered_left.insert(*item);
+            size -= l + r;
+        }
+    }
+
+    let moves = types
+        .iter()
+        .filter_map(|item| {
+            let (l, r) = count(*item);
+            let (moved, direction) = if gathered_left.contains(*item) {
+                (r, Direction::RightToLeft)
+            } else {
+                (l, Direction::LeftToRight)
+            };
+            (moved > 0).then_some(ItemMove {
+                item: *item,
+                count: moved,
+                direction,
+            })
+        })
+        .collect();
+
+    let gathered_right = all_types.difference(&gathered_left);
+    let stays_left = |item: &char| gathered_left.contains(*item);
+    let goes_right = |item: &char| gathered_right.contains(*item);
+    let corrected = left
+        .chars()
+        .filter(stays_left)
+        .chain(right.chars().filter(stays_left))
+        .chain(right.chars().filter(goes_right))
+        .chain(left.chars().filter(goes_right))
+        .collect();
+
+    Some(Repack { corrected, moves })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted(items: &str) -> Vec<char> {
+        let mut items: Vec<char> = items.chars().collect();
+        items.sort();
+        items
+    }
+
+    #[test]
+    fn separates_the_shared_items_of_the_first_example() {
+        let line = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let repack = repack(line).unwrap();
+        assert_eq!(repack.total_moves(), 2);
+
+        let (left, right) = repack.corrected.split_at(line.len() / 2);
+        let (left, right) = (ItemSet::try_from(left), ItemSet::try_from(right));
+        assert!(left.unwrap().intersection(&right.unwrap()).is_empty());
+        assert_eq!(sorted(&repack.corrected), sorted(line));
+    }
+
+    #[test]
+    fn cannot_balance_a_type_filling_more_than_a_compartment() {
+        assert!(repack("aaab").is_none());
+    }
+}
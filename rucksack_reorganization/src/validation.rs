@@ -0,0 +1,83 @@
+use std::fmt;
+
+use crate::item_set::ItemSet;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum RucksackError {
+    OddLength(usize),
+    InvalidCharacter { item: char, position: usize },
+    NoSharedItem,
+    MultipleSharedItems(Vec<char>),
+}
+
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RucksackError::OddLength(length) => {
+                write!(f, "odd number of items ({}), compartments differ", length)
+            }
+            RucksackError::InvalidCharacter { item, position } => {
+                write!(f, "invalid item {:?} at position {}", item, position + 1)
+            }
+            RucksackError::NoSharedItem => write!(f, "no item shared by both compartments"),
+            RucksackError::MultipleSharedItems(items) => {
+                let items: String = items.iter().collect();
+                write!(f, "several items shared by both compartments: {}", items)
+            }
+        }
+    }
+}
+
+/// Returns the single item type found in both compartments of a rucksack.
+pub fn validate(line: &str) -> Result<char, RucksackError> {
+    ItemSet::try_from(line)?;
+    if !line.len().is_multiple_of(2) {
+        return Err(RucksackError::OddLength(line.len()));
+    }
+
+    let (left, right) = line.split_at(line.len() / 2);
+    let shared: Vec<char> = ItemSet::try_from(left)?
+        .intersection(&ItemSet::try_from(right)?)
+        .iter()
+        .collect();
+
+    match shared[..] {
+        [] => Err(RucksackError::NoSharedItem),
+        [item] => Ok(item),
+        _ => Err(RucksackError::MultipleSharedItems(shared)),
+    }
+}
+
+/// Validation outcome of every rucksack in a file, keyed by 1-based line number.
+pub struct ValidationReport {
+    pub valid: Vec<(usize, char)>,
+    pub errors: Vec<(usize, RucksackError)>,
+}
+
+impl ValidationReport {
+    pub fn new(lines: &[String]) -> Self {
+        let mut valid = vec![];
+        let mut errors = vec![];
+        for (index, line) in lines.iter().enumerate() {
+            match validate(line) {
+                Ok(item) => valid.push((index + 1, item)),
+                Err(error) => errors.push((index + 1, error)),
+            }
+        }
+        ValidationReport { valid, errors }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (line, error) in &self.errors {
+            writeln!(f, "line {}: {}", line, error)?;
+        }
+        writeln!(
+            f,
+            "{} valid rucksacks, {} invalid",
+            self.valid.len(),
+            self.errors.len()
+        )
+    }
+}
@@ -0,0 +1,82 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use supply_stacks::crane::{naive_9000, naive_9001, CrateMover, CrateMover9000, CrateMover9001};
+use supply_stacks::{Crate, Move};
+use utils::Rng;
+
+const STACKS: usize = 9;
+const MOVES: usize = 1_000_000;
+const MAX_QUANTITY: usize = 100;
+
+/// Stacks of `max_quantity` random crates and a legal program of `moves` moves of up to
+/// `max_quantity` crates each.
+fn generate(moves: usize, max_quantity: usize, rng: &mut Rng) -> (Vec<Vec<Crate>>, Vec<Move>) {
+    let stacks: Vec<Vec<Crate>> = (0..STACKS)
+        .map(|_| {
+            (0..max_quantity)
+                .map(|_| ((b'A' + rng.below(26) as u8) as char).to_string())
+                .collect()
+        })
+        .collect();
+    let mut lengths: Vec<usize> = stacks.iter().map(|stack| stack.len()).collect();
+    let program = (0..moves)
+        .map(|_| {
+            let from = loop {
+                let from = rng.below(STACKS as u64) as usize;
+                if lengths[from] > 0 {
+                    break from;
+                }
+            };
+            let to = (from + 1 + rng.below(STACKS as u64 - 1) as usize) % STACKS;
+            let quantity = 1 + rng.below(lengths[from].min(max_quantity) as u64) as usize;
+            lengths[from] -= quantity;
+            lengths[to] += quantity;
+            Move {
+                quantity: quantity as i64,
+                from: from as i64 + 1,
+                to: to as i64 + 1,
+            }
+        })
+        .collect();
+    (stacks, program)
+}
+
+fn run(stacks: &[Vec<Crate>], program: &[Move], step: impl Fn(&mut [Vec<Crate>], &Move)) {
+    let mut stacks = stacks.to_vec();
+    for m in program {
+        step(&mut stacks, m);
+    }
+}
+
+/// The bulk cranes against the crate-by-crate reference on a generated program.
+fn cranes(c: &mut Criterion) {
+    let (stacks, program) = generate(MOVES, MAX_QUANTITY, &mut Rng::new(2022));
+    let crates: i64 = program.iter().map(|m| m.quantity).sum();
+
+    let mut group = c.benchmark_group("cranes");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(crates as u64));
+    group.bench_function("CrateMover9000 bulk", |b| {
+        b.iter(|| {
+            run(&stacks, &program, |stacks, m| {
+                CrateMover9000 {}.move_crates(stacks, m);
+            })
+        })
+    });
+    group.bench_function("CrateMover9000 naive", |b| {
+        b.iter(|| run(&stacks, &program, naive_9000))
+    });
+    group.bench_function("CrateMover9001 bulk", |b| {
+        b.iter(|| {
+            run(&stacks, &program, |stacks, m| {
+                CrateMover9001 {}.move_crates(stacks, m);
+            })
+        })
+    });
+    group.bench_function("CrateMover9001 naive", |b| {
+        b.iter(|| run(&stacks, &program, naive_9001))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cranes);
+criterion_main!(benches);
@@ -0,0 +1,276 @@
+use std::ops::AddAssign;
+
+use crate::{Crate, Move, SupplyStack};
+
+/// Crane effort: how many times it lifted and how many crates it carried in total.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Work {
+    pub lifts: usize,
+    pub crates: usize,
+}
+
+impl AddAssign for Work {
+    fn add_assign(&mut self, other: Work) {
+        self.lifts += other.lifts;
+        self.crates += other.crates;
+    }
+}
+
+/// Simulated time units spent per lift and per crate carried.
+#[derive(Debug, Copy, Clone)]
+pub struct CostModel {
+    pub per_lift: u64,
+    pub per_crate: u64,
+}
+
+impl CostModel {
+    pub fn cost(&self, work: Work) -> u64 {
+        work.lifts as u64 * self.per_lift + work.crates as u64 * self.per_crate
+    }
+}
+
+pub trait CrateMover {
+    /// Moves the top `m.quantity` crates of `m.from` onto `m.to`, touching nothing else.
+    fn move_crates(&self, stacks: &mut [Vec<Crate>], m: &Move) -> Work;
+
+    fn execute_moves(&self, supply_stack: &mut SupplyStack) -> Work {
+        let mut work = Work::default();
+        for m in &supply_stack.moves {
+            work += self.move_crates(&mut supply_stack.stacks, m);
+        }
+        work
+    }
+}
+
+/// Both stacks of a move, borrowed together so crates can be drained from one into the other.
+/// `None` when a move puts crates back on the stack they came from, which changes nothing.
+fn from_and_to<'a>(
+    stacks: &'a mut [Vec<Crate>],
+    m: &Move,
+) -> Option<(&'a mut Vec<Crate>, &'a mut Vec<Crate>)> {
+    let (from, to) = (m.source(), m.target());
+    if from < to {
+        let (left, right) = stacks.split_at_mut(to);
+        Some((&mut left[from], &mut right[0]))
+    } else if from > to {
+        let (left, right) = stacks.split_at_mut(from);
+        Some((&mut right[0], &mut left[to]))
+    } else {
+        None
+    }
+}
+
+/// Lifts one crate at a time.
+pub struct CrateMover9000 {}
+
+impl CrateMover for CrateMover9000 {
+    fn move_crates(&self, stacks: &mut [Vec<Crate>], m: &Move) -> Work {
+        if let Some((from, to)) = from_and_to(stacks, m) {
+            to.extend(from.drain(from.len() - m.quantity as usize..).rev());
+        }
+        Work {
+            lifts: m.quantity as usize,
+            crates: m.quantity as usize,
+        }
+    }
+}
+
+/// Lifts all crates of a move at once, keeping their order.
+pub struct CrateMover9001 {}
+
+impl CrateMover for CrateMover9001 {
+    fn move_crates(&self, stacks: &mut [Vec<Crate>], m: &Move) -> Work {
+        if let Some((from, to)) = from_and_to(stacks, m) {
+            to.extend(from.drain(from.len() - m.quantity as usize..));
+        }
+        Work {
+            lifts: (m.quantity > 0) as usize,
+            crates: m.quantity as usize,
+        }
+    }
+}
+
+// The cranes as they were before moving crates in bulk, kept as the reference.
+
+pub fn naive_9000(stacks: &mut [Vec<Crate>], m: &Move) {
+    for _ in 0..m.quantity {
+        let c = stacks[m.source()].pop().unwrap();
+        stacks[m.target()].push(c);
+    }
+}
+
+pub fn naive_9001(stacks: &mut [Vec<Crate>], m: &Move) {
+    let len = stacks[m.source()].len();
+    let mut crates = stacks[m.source()][(len - m.quantity as usize)..].to_vec();
+    stacks[m.target()].append(&mut crates);
+    for _ in 0..m.quantity {
+        stacks[m.source()].pop().unwrap();
+    }
+}
+
+/// Lifts at most `capacity` crates at a time, keeping the order within each lift.
+pub struct CapacityCrane {
+    pub capacity: usize,
+}
+
+/// Like `CapacityCrane`, but every second lift of a move is put down upside down.
+///
+/// Lifts are counted per move, so the crane carries nothing over between moves and a program
+/// can be stepped back and forth. Nothing is reversed unless a move takes more than one lift,
+/// which is why the capacity has to be given and be at least 2.
+pub struct AlternatingCrane {
+    pub capacity: usize,
+}
+
+fn lift_in_batches(stacks: &mut [Vec<Crate>], m: &Move, capacity: usize, alternate: bool) -> Work {
+    let mut remaining = m.quantity as usize;
+    let mut lifts = 0;
+    while remaining > 0 {
+        let batch = remaining.min(capacity);
+        let from = &mut stacks[m.source()];
+        let mut crates = from.split_off(from.len() - batch);
+        if alternate && lifts % 2 == 1 {
+            crates.reverse();
+        }
+        stacks[m.target()].extend(crates);
+        remaining -= batch;
+        lifts += 1;
+    }
+    Work {
+        lifts,
+        crates: m.quantity as usize,
+    }
+}
+
+impl CrateMover for CapacityCrane {
+    fn move_crates(&self, stacks: &mut [Vec<Crate>], m: &Move) -> Work {
+        lift_in_batches(stacks, m, self.capacity, false)
+    }
+}
+
+impl CrateMover for AlternatingCrane {
+    fn move_crates(&self, stacks: &mut [Vec<Crate>], m: &Move) -> Work {
+        lift_in_batches(stacks, m, self.capacity, true)
+    }
+}
+
+/// Crane named on the command line: `9000`, `9001`, `capacity[:<k>]` or `alternating:<k>`.
+pub fn from_name(name: &str) -> Box<dyn CrateMover> {
+    let (model, capacity) = match name.split_once(':') {
+        Some((model, capacity)) => (model, capacity.parse().unwrap()),
+        None if name == "alternating" => {
+            panic!("alternating crane needs a capacity, e.g. alternating:3")
+        }
+        None => (name, usize::MAX),
+    };
+    if capacity == 0 {
+        panic!("crane capacity must be at least 1");
+    }
+    if model == "alternating" && capacity < 2 {
+        panic!("alternating crane needs a capacity of at least 2 to reverse anything");
+    }
+    match model {
+        "9000" => Box::new(CrateMover9000 {}),
+        "9001" => Box::new(CrateMover9001 {}),
+        "capacity" => Box::new(CapacityCrane { capacity }),
+        "alternating" => Box::new(AlternatingCrane { capacity }),
+        _ => panic!("unknown crane {:?}", name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::Rng;
+
+    /// Stacks of random crates and a legal program of `moves` moves, some of them putting crates
+    /// back where they came from.
+    fn generate(moves: usize, rng: &mut Rng) -> (Vec<Vec<Crate>>, Vec<Move>) {
+        let stacks: Vec<Vec<Crate>> = (0..9)
+            .map(|_| {
+                (0..20)
+                    .map(|c: u8| ((b'A' + c) as char).to_string())
+                    .collect()
+            })
+            .collect();
+        let mut lengths: Vec<usize> = stacks.iter().map(|stack| stack.len()).collect();
+        let program = (0..moves)
+            .map(|_| {
+                let from = loop {
+                    let from = rng.below(9) as usize;
+                    if lengths[from] > 0 {
+                        break from;
+                    }
+                };
+                let to = rng.below(9) as usize;
+                let quantity = 1 + rng.below(lengths[from] as u64) as usize;
+                lengths[from] -= quantity;
+                lengths[to] += quantity;
+                Move {
+                    quantity: quantity as i64,
+                    from: from as i64 + 1,
+                    to: to as i64 + 1,
+                }
+            })
+            .collect();
+        (stacks, program)
+    }
+
+    fn run(
+        stacks: &[Vec<Crate>],
+        program: &[Move],
+        mut step: impl FnMut(&mut [Vec<Crate>], &Move),
+    ) -> Vec<Vec<Crate>> {
+        let mut stacks = stacks.to_vec();
+        for m in program {
+            step(&mut stacks, m);
+        }
+        stacks
+    }
+
+    #[test]
+    fn bulk_moves_match_the_crate_by_crate_cranes() {
+        let mut rng = Rng::new(44);
+        for moves in [1, 10, 1000, 100_000] {
+            let (stacks, program) = generate(moves, &mut rng);
+            assert_eq!(
+                run(&stacks, &program, |stacks, m| {
+                    CrateMover9000 {}.move_crates(stacks, m);
+                }),
+                run(&stacks, &program, naive_9000),
+                "CrateMover9000, {} moves",
+                moves
+            );
+            assert_eq!(
+                run(&stacks, &program, |stacks, m| {
+                    CrateMover9001 {}.move_crates(stacks, m);
+                }),
+                run(&stacks, &program, naive_9001),
+                "CrateMover9001, {} moves",
+                moves
+            );
+        }
+    }
+
+    #[test]
+    fn alternating_crane_reverses_every_second_lift_of_a_move() {
+        let mut stacks = vec!["abcde".chars().map(String::from).collect(), vec![]];
+        let m = Move {
+            quantity: 5,
+            from: 1,
+            to: 2,
+        };
+        let work = AlternatingCrane { capacity: 2 }.move_crates(&mut stacks, &m);
+        assert_eq!(
+            stacks[1],
+            "decba".chars().map(String::from).collect::<Vec<Crate>>()
+        );
+        assert_eq!(
+            work,
+            Work {
+                lifts: 3,
+                crates: 5
+            }
+        );
+    }
+}
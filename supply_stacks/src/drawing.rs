@@ -0,0 +1,331 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::Crate;
+
+/// Why a drawing could not be read, with the 1-based line it was found on.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DrawingError {
+    Empty,
+    /// No blank line between the drawing and the moves.
+    MissingSeparator,
+    BadFooter {
+        line: usize,
+        found: String,
+    },
+    Unexpected {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    UnclosedCrate {
+        line: usize,
+        column: usize,
+    },
+    EmptyLabel {
+        line: usize,
+        column: usize,
+    },
+    NotUnderAStack {
+        line: usize,
+        column: usize,
+    },
+    SharedColumn {
+        line: usize,
+        stack: usize,
+    },
+    Floating {
+        line: usize,
+        stack: usize,
+    },
+}
+
+impl fmt::Display for DrawingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DrawingError::Empty => write!(f, "drawing has no stack numbers"),
+            DrawingError::MissingSeparator => {
+                write!(f, "no blank line between the drawing and the moves")
+            }
+            DrawingError::BadFooter { line, found } => {
+                write!(
+                    f,
+                    "line {}: expected stack numbers 1, 2, ... but found {:?}",
+                    line, found
+                )
+            }
+            DrawingError::Unexpected {
+                line,
+                column,
+                found,
+            } => write!(f, "line {} column {}: unexpected {:?}", line, column, found),
+            DrawingError::UnclosedCrate { line, column } => {
+                write!(
+                    f,
+                    "line {} column {}: crate is missing its `]`",
+                    line, column
+                )
+            }
+            DrawingError::EmptyLabel { line, column } => {
+                write!(f, "line {} column {}: crate has no label", line, column)
+            }
+            DrawingError::NotUnderAStack { line, column } => write!(
+                f,
+                "line {} column {}: crate is not above any stack number",
+                line, column
+            ),
+            DrawingError::SharedColumn { line, stack } => {
+                write!(f, "line {}: two crates above stack {}", line, stack)
+            }
+            DrawingError::Floating { line, stack } => write!(
+                f,
+                "line {}: crate on stack {} has nothing underneath",
+                line, stack
+            ),
+        }
+    }
+}
+
+/// Replaces tabs with spaces up to the next multiple of 4, the width of one drawn stack.
+fn expand_tabs(line: &str) -> Vec<char> {
+    let mut chars = vec![];
+    for c in line.chars() {
+        if c == '\t' {
+            chars.push(' ');
+            while chars.len() % 4 != 0 {
+                chars.push(' ');
+            }
+        } else {
+            chars.push(c);
+        }
+    }
+    chars
+}
+
+/// Column span of every number on the footer line, which must count 1, 2, ... in order.
+fn footer(line: &[char], line_number: usize) -> Result<Vec<RangeInclusive<usize>>, DrawingError> {
+    let mut spans = vec![];
+    let mut column = 0;
+    while column < line.len() {
+        if line[column] == ' ' {
+            column += 1;
+            continue;
+        }
+        let start = column;
+        while column < line.len() && line[column] != ' ' {
+            column += 1;
+        }
+        let number: String = line[start..column].iter().collect();
+        if number.parse::<usize>().ok() != Some(spans.len() + 1) {
+            return Err(DrawingError::BadFooter {
+                line: line_number,
+                found: number,
+            });
+        }
+        spans.push(start..=column - 1);
+    }
+    if spans.is_empty() {
+        return Err(DrawingError::Empty);
+    }
+    Ok(spans)
+}
+
+/// Every `[label]` on a line with the columns it spans.
+fn crates(
+    line: &[char],
+    line_number: usize,
+) -> Result<Vec<(RangeInclusive<usize>, String)>, DrawingError> {
+    let mut crates = vec![];
+    let mut column = 0;
+    while column < line.len() {
+        match line[column] {
+            ' ' => column += 1,
+            '[' => {
+                let start = column;
+                let end = (start..line.len()).find(|c| line[*c] == ']').ok_or(
+                    DrawingError::UnclosedCrate {
+                        line: line_number,
+                        column: start + 1,
+                    },
+                )?;
+                let label: String = line[start + 1..end].iter().collect();
+                if label.trim().is_empty() {
+                    return Err(DrawingError::EmptyLabel {
+                        line: line_number,
+                        column: start + 1,
+                    });
+                }
+                crates.push((start..=end, label.trim().to_string()));
+                column = end + 1;
+            }
+            found => {
+                return Err(DrawingError::Unexpected {
+                    line: line_number,
+                    column: column + 1,
+                    found,
+                })
+            }
+        }
+    }
+    Ok(crates)
+}
+
+/// Reads the stacks from a drawing, bottom crate first.
+///
+/// Stack positions come from the numbered footer, and a crate belongs to the one stack number
+/// its brackets overlap, so lines may be trimmed, indented with tabs or wider than 9 stacks.
+/// Labels may be longer than one character as long as the brackets stay above a single stack
+/// number, which is how `render::drawing` lays them out.
+pub fn parse(lines: &[String]) -> Result<Vec<Vec<Crate>>, DrawingError> {
+    let (footer_line, crate_lines) = lines.split_last().ok_or(DrawingError::Empty)?;
+    let spans = footer(&expand_tabs(footer_line), lines.len())?;
+    let mut stacks = vec![vec![]; spans.len()];
+
+    for (height, (index, line)) in crate_lines.iter().enumerate().rev().enumerate() {
+        let line_number = index + 1;
+        for (columns, label) in crates(&expand_tabs(line), line_number)? {
+            let mut under = spans
+                .iter()
+                .enumerate()
+                .filter(|(_, span)| span.start() <= columns.end() && columns.start() <= span.end());
+            let stack = match (under.next(), under.next()) {
+                (Some((stack, _)), None) => stack,
+                _ => {
+                    return Err(DrawingError::NotUnderAStack {
+                        line: line_number,
+                        column: columns.start() + 1,
+                    })
+                }
+            };
+            if stacks[stack].len() > height {
+                return Err(DrawingError::SharedColumn {
+                    line: line_number,
+                    stack: stack + 1,
+                });
+            }
+            if stacks[stack].len() < height {
+                return Err(DrawingError::Floating {
+                    line: line_number,
+                    stack: stack + 1,
+                });
+            }
+            stacks[stack].push(label);
+        }
+    }
+
+    Ok(stacks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_lines(lines: &[&str]) -> Result<Vec<Vec<Crate>>, DrawingError> {
+        parse(
+            &lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<String>>(),
+        )
+    }
+
+    fn stacks(stacks: &[&str]) -> Vec<Vec<Crate>> {
+        stacks
+            .iter()
+            .map(|stack| stack.chars().map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn reads_trimmed_lines() {
+        let drawing = ["    [D]", "[N] [C]", "[Z] [M] [P]", " 1   2   3"];
+        assert_eq!(parse_lines(&drawing), Ok(stacks(&["ZN", "MCD", "P"])));
+    }
+
+    #[test]
+    fn expands_tabs_to_stack_width() {
+        let drawing = ["\t[D]\t", "[N]\t[C]", "[Z] [M] [P]", " 1   2   3 "];
+        assert_eq!(parse_lines(&drawing), Ok(stacks(&["ZN", "MCD", "P"])));
+    }
+
+    #[test]
+    fn reads_more_than_nine_stacks() {
+        let drawing = [
+            "                                    [J]     [L]",
+            "[A] [B] [C] [D] [E] [F] [G] [H] [I] [K]     [M]",
+            " 1   2   3   4   5   6   7   8   9  10  11  12",
+        ];
+        assert_eq!(
+            parse_lines(&drawing),
+            Ok(stacks(&[
+                "A", "B", "C", "D", "E", "F", "G", "H", "I", "KJ", "", "ML"
+            ]))
+        );
+    }
+
+    #[test]
+    fn reads_labels_longer_than_a_letter() {
+        let drawing = ["     [XY]", "[AB] [C]", " 1    2"];
+        assert_eq!(
+            parse_lines(&drawing),
+            Ok(vec![
+                vec!["AB".to_string()],
+                vec!["C".to_string(), "XY".to_string()]
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_bad_footers() {
+        assert_eq!(parse_lines(&[]), Err(DrawingError::Empty));
+        assert_eq!(parse_lines(&["[A]", "   "]), Err(DrawingError::Empty));
+        assert_eq!(
+            parse_lines(&["[A]", " 1   3"]),
+            Err(DrawingError::BadFooter {
+                line: 2,
+                found: "3".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_crates() {
+        let footer = " 1   2";
+        let cases = [
+            (
+                "[A] x",
+                DrawingError::Unexpected {
+                    line: 1,
+                    column: 5,
+                    found: 'x',
+                },
+            ),
+            ("    [A", DrawingError::UnclosedCrate { line: 1, column: 5 }),
+            ("[ ]", DrawingError::EmptyLabel { line: 1, column: 1 }),
+            (
+                "[ABCDE]",
+                DrawingError::NotUnderAStack { line: 1, column: 1 },
+            ),
+            (
+                "        [A]",
+                DrawingError::NotUnderAStack { line: 1, column: 9 },
+            ),
+        ];
+        for (line, error) in cases {
+            assert_eq!(parse_lines(&[line, footer]), Err(error), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn rejects_misplaced_crates() {
+        let footer = " 1   2   3   4   5   6   7   8   9  10";
+        assert_eq!(
+            parse_lines(&["                                  [A][B]", footer]),
+            Err(DrawingError::SharedColumn { line: 1, stack: 10 })
+        );
+        assert_eq!(
+            parse_lines(&["    [A]", "[B]", footer]),
+            Err(DrawingError::Floating { line: 1, stack: 2 })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::crane::CrateMover;
+use crate::validate::{self, IllegalMove};
+use crate::{Crate, Move, SupplyStack};
+
+/// What a move changed, enough to put the stacks back the way they were.
+struct Entry {
+    m: Move,
+    // top crates of `from` before the move, at most `m.quantity` of them
+    lifted: Vec<Crate>,
+    from_len: usize,
+    to_len: usize,
+}
+
+/// Runs a crane program one move at a time, recording every applied move so it can be undone.
+pub struct Journal<'a> {
+    crane: &'a dyn CrateMover,
+    stacks: Vec<Vec<Crate>>,
+    program: Vec<Move>,
+    applied: Vec<Entry>,
+}
+
+impl<'a> Journal<'a> {
+    pub fn new(supply_stack: SupplyStack, crane: &'a dyn CrateMover) -> Self {
+        Journal {
+            crane,
+            stacks: supply_stack.stacks,
+            program: supply_stack.moves,
+            applied: vec![],
+        }
+    }
+
+    /// Number of moves applied so far.
+    pub fn step(&self) -> usize {
+        self.applied.len()
+    }
+
+    /// Number of moves in the program.
+    pub fn len(&self) -> usize {
+        self.program.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.program.is_empty()
+    }
+
+    pub fn stacks(&self) -> &[Vec<Crate>] {
+        &self.stacks
+    }
+
+    /// The move the next `redo` would apply.
+    pub fn next_move(&self) -> Option<&Move> {
+        self.program.get(self.step())
+    }
+
+    /// Why the next move of the program cannot be applied, Ok at the end of the program too.
+    pub fn check_next(&self) -> Result<(), IllegalMove> {
+        self.next_move()
+            .map_or(Ok(()), |m| validate::check(&self.stacks, m))
+    }
+
+    /// Applies the next move of the program, which has to pass `check_next`.
+    pub fn redo(&mut self) -> Option<Move> {
+        let m = *self.next_move()?;
+        let from = &self.stacks[m.source()];
+        let lifted = from[from.len().saturating_sub(m.quantity as usize)..].to_vec();
+        let entry = Entry {
+            m,
+            lifted,
+            from_len: from.len(),
+            to_len: self.stacks[m.target()].len(),
+        };
+        self.crane.move_crates(&mut self.stacks, &m);
+        self.applied.push(entry);
+        Some(m)
+    }
+
+    /// Takes back the last applied move.
+    pub fn undo(&mut self) -> Option<Move> {
+        let entry = self.applied.pop()?;
+        self.stacks[entry.m.target()].truncate(entry.to_len);
+        let from = &mut self.stacks[entry.m.source()];
+        from.truncate(entry.from_len - entry.lifted.len());
+        from.extend(entry.lifted);
+        Some(entry.m)
+    }
+
+    /// Applies `m` at the current step instead of the rest of the program, like an edit
+    /// discards everything that could have been redone. `m` has to pass `validate::check`.
+    pub fn apply(&mut self, m: Move) {
+        self.program.truncate(self.step());
+        self.program.push(m);
+        self.redo();
+    }
+
+    /// Undoes or redoes moves until exactly `step` moves are applied, stopping early in front of
+    /// a move that cannot be applied.
+    pub fn seek(&mut self, step: usize) {
+        let step = step.min(self.len());
+        while self.step() > step {
+            self.undo();
+        }
+        while self.step() < step && self.check_next().is_ok() {
+            self.redo();
+        }
+    }
+
+    /// The stacks after the first `step` moves of the program.
+    pub fn state_at(&mut self, step: usize) -> Vec<Vec<Crate>> {
+        self.seek(step);
+        self.stacks.clone()
+    }
+
+    /// Rewinds to `step` and runs the rest of the program from there.
+    pub fn replay_from(&mut self, step: usize) -> &[Vec<Crate>] {
+        self.seek(step);
+        self.seek(self.len());
+        &self.stacks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::CrateMover9000;
+
+    fn journal(moves: &[&str]) -> Journal<'static> {
+        let supply_stack = SupplyStack {
+            stacks: vec![
+                vec!["A".to_string(), "B".to_string()],
+                vec!["C".to_string()],
+            ],
+            moves: moves.iter().map(|line| Move::new(line)).collect(),
+            first_move_line: 1,
+        };
+        Journal::new(supply_stack, &CrateMover9000 {})
+    }
+
+    #[test]
+    fn seek_stops_in_front_of_an_illegal_move() {
+        let mut journal = journal(&[
+            "move 1 from 1 to 2",
+            "move 99 from 1 to 2",
+            "move 1 from 2 to 1",
+        ]);
+        journal.seek(3);
+        assert_eq!(journal.step(), 1);
+        assert_eq!(
+            journal.check_next(),
+            Err(IllegalMove::NotEnoughCrates { available: 1 })
+        );
+        assert_eq!(journal.stacks(), [vec!["A"], vec!["C", "B"]]);
+    }
+
+    #[test]
+    fn check_next_is_ok_at_the_end_of_the_program() {
+        let mut journal = journal(&["move 2 from 1 to 2"]);
+        journal.seek(1);
+        assert_eq!(journal.check_next(), Ok(()));
+        assert_eq!(journal.redo(), None);
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+
+pub mod crane;
+pub mod drawing;
+pub mod journal;
+pub mod plan;
+pub mod render;
+pub mod validate;
+
+use drawing::DrawingError;
+
+/// Label of one crate, usually a single letter.
+pub type Crate = String;
+
+/// One line of the crane program, stacks numbered from 1 as in the drawing. Numbers are kept
+/// as written, so a program naming stack 0 or -1 still parses and `validate` can report it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub quantity: i64,
+    pub from: i64,
+    pub to: i64,
+}
+
+impl Move {
+    pub fn new(input_line: &str) -> Self {
+        Move::parse(input_line).unwrap_or_else(|| panic!("malformed move {:?}", input_line))
+    }
+
+    /// A line of the form `move <quantity> from <stack> to <stack>`, or None for anything else.
+    pub fn parse(input_line: &str) -> Option<Self> {
+        let words: Vec<&str> = input_line.split_whitespace().collect();
+        match words[..] {
+            ["move", quantity, "from", from, "to", to] => Some(Move {
+                quantity: quantity.parse().ok()?,
+                from: from.parse().ok()?,
+                to: to.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Index of the stack crates are taken from, for a move that passed `validate::check`.
+    pub fn source(&self) -> usize {
+        self.from as usize - 1
+    }
+
+    /// Index of the stack crates are put on, for a move that passed `validate::check`.
+    pub fn target(&self) -> usize {
+        self.to as usize - 1
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.quantity, self.from, self.to
+        )
+    }
+}
+
+pub fn stack_top(stacks: &[Vec<Crate>]) -> String {
+    stacks
+        .iter()
+        .filter_map(|stack| stack.last().map(String::as_str))
+        .collect()
+}
+
+#[derive(Debug)]
+pub struct SupplyStack {
+    pub stacks: Vec<Vec<Crate>>,
+    pub moves: Vec<Move>,
+    /// 1-based input line of the first move.
+    pub first_move_line: usize,
+}
+
+impl SupplyStack {
+    pub fn stack_from_input(stack_input: &[String]) -> Result<Vec<Vec<Crate>>, DrawingError> {
+        drawing::parse(stack_input)
+    }
+
+    fn moves_from_input(moves_input: &[String]) -> Vec<Move> {
+        moves_input.iter().map(|line| Move::new(line)).collect()
+    }
+
+    pub fn from_input(input: &[String]) -> Result<Self, DrawingError> {
+        let stack_move_split = input
+            .iter()
+            .position(|s| s.trim().is_empty())
+            .ok_or(DrawingError::MissingSeparator)?;
+        Ok(SupplyStack {
+            stacks: SupplyStack::stack_from_input(&input[..stack_move_split])?,
+            moves: SupplyStack::moves_from_input(&input[(stack_move_split + 1usize)..]),
+            first_move_line: stack_move_split + 2usize,
+        })
+    }
+
+    pub fn stack_top(&self) -> String {
+        stack_top(&self.stacks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn drawing_needs_a_blank_line_before_the_moves() {
+        let supply_stack =
+            SupplyStack::from_input(&input(&["[A]", " 1 ", "  ", "move 1 from 1 to 1"]));
+        assert_eq!(supply_stack.unwrap().first_move_line, 4);
+        assert_eq!(
+            SupplyStack::from_input(&input(&["[A]", " 1 ", "move 1 from 1 to 1"])).unwrap_err(),
+            DrawingError::MissingSeparator
+        );
+    }
+
+    #[test]
+    fn parses_moves_and_rejects_malformed_lines() {
+        assert_eq!(
+            Move::parse("move 3 from 1 to 12"),
+            Some(Move {
+                quantity: 3,
+                from: 1,
+                to: 12
+            })
+        );
+        assert_eq!(
+            Move::parse("move 1 from -2 to 0"),
+            Some(Move {
+                quantity: 1,
+                from: -2,
+                to: 0
+            })
+        );
+        for line in ["move x", "move", "move 1 from 2", "goto 4"] {
+            assert_eq!(Move::parse(line), None, "{:?}", line);
+        }
+    }
+}
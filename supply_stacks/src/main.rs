@@ -1,123 +1,241 @@
 use std::fs::File;
+use std::io::{self, BufRead};
 use utils::read_lines;
 
-#[derive(Debug, Clone, Copy)]
-struct Move {
-    quantity: i64,
-    from: usize,
-    to: usize,
-}
+use supply_stacks::crane::{self, CostModel, CrateMover, CrateMover9000, CrateMover9001};
+use supply_stacks::journal::Journal;
+use supply_stacks::plan::{self, Goal, Outcome};
+use supply_stacks::validate::{self, Mode};
+use supply_stacks::{render, stack_top, Crate, Move, SupplyStack};
 
-impl Move {
-    pub fn new(input_line: &String) -> Self {
-        let digits: Vec<i64> = input_line
-            .split(" ")
-            .skip(1)
-            .step_by(2)
-            .map(|c| c.parse::<i64>().unwrap())
-            .collect();
-        if let [quantity, from, to] = digits[..] {
-            Move {
-                quantity,
-                from: from as usize - 1usize,
-                to: to as usize - 1usize,
-            }
-        } else {
-            panic!()
-        }
+fn print_stacks(stacks: &[Vec<Crate>]) {
+    for line in render::drawing(stacks) {
+        println!("{}", line);
     }
 }
 
-#[derive(Debug)]
-struct SupplyStack {
-    stacks: Vec<Vec<char>>,
-    moves: Vec<Move>,
-}
-
-impl SupplyStack {
-    fn stack_from_input(stack_input: &[String]) -> Vec<Vec<char>> {
-        // 0, 1, 2, 3, 4, 5
-        //             [  X
-        // [  N  ]     [  C
-        // 1 -> stack 0 -> 0 * 4 + 1
-        // 5 -> stack 1 -> 1 * 4 + 1
-
-        let total_stacks = ((stack_input.last().unwrap().len() - 1) / 4) + 1;
-        let mut stacks = vec![vec!['X'; 0]; total_stacks];
-
-        for line in stack_input.iter().rev().skip(1) {
-            for (index, c) in line
-                .chars()
-                .enumerate()
-                .skip(1)
-                .step_by(4)
-                .filter(|(_, c)| *c != ' ')
-            {
-                let stack_index = (index - 1usize) / 4;
-                stacks[stack_index].push(c);
+/// Steps through the crane program with commands read from stdin.
+fn debug_program(mut journal: Journal) {
+    println!("commands: next, back, goto <step>, replay <step>, print, <move line>, quit");
+    for line in io::stdin().lock().lines() {
+        let line = line.unwrap();
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some("next") | Some("n") => match journal.check_next() {
+                Err(reason) => {
+                    println!("cannot apply {}: {}", journal.next_move().unwrap(), reason)
+                }
+                Ok(()) => match journal.redo() {
+                    Some(m) => println!("applied: {}", m),
+                    None => println!("end of program"),
+                },
+            },
+            Some("back") | Some("b") => match journal.undo() {
+                Some(m) => println!("undone: {}", m),
+                None => println!("start of program"),
+            },
+            Some(command @ ("goto" | "g" | "replay")) => {
+                let Some(step) = words.next().and_then(|step| step.parse().ok()) else {
+                    println!("usage: {} <step>", command);
+                    continue;
+                };
+                if command == "replay" {
+                    journal.replay_from(step);
+                } else {
+                    journal.seek(step);
+                }
+                if let Err(reason) = journal.check_next() {
+                    println!(
+                        "stopped before {}: {}",
+                        journal.next_move().unwrap(),
+                        reason
+                    );
+                }
             }
+            Some("print") | Some("p") => print_stacks(journal.stacks()),
+            Some("move") => match Move::parse(&line) {
+                None => println!("usage: move <quantity> from <stack> to <stack>"),
+                Some(m) => match validate::check(journal.stacks(), &m) {
+                    Err(reason) => println!("cannot apply {}: {}", m, reason),
+                    Ok(()) => journal.apply(m),
+                },
+            },
+            Some("quit") | Some("q") => break,
+            _ => continue,
         }
-
-        stacks
-    }
-
-    fn moves_from_input(moves_input: &[String]) -> Vec<Move> {
-        moves_input.iter().map(|line| Move::new(line)).collect()
-    }
-
-    pub fn from_input(input: &Vec<String>) -> Self {
-        let stack_move_split = input.iter().position(|s| s == "").unwrap();
-        SupplyStack {
-            stacks: SupplyStack::stack_from_input(&input[..stack_move_split]),
-            moves: SupplyStack::moves_from_input(&input[(stack_move_split + 1usize)..]),
-        }
+        println!(
+            "step {}/{} - top {:?}{}",
+            journal.step(),
+            journal.len(),
+            stack_top(journal.stacks()),
+            journal
+                .next_move()
+                .map_or(String::new(), |m| format!(" - next: {}", m))
+        );
     }
+}
 
-    pub fn stack_top(&self) -> String {
-        self.stacks
-            .iter()
-            .map(|stack| stack.last())
-            .filter_map(|x| x)
-            .collect()
+/// Crate labels from the command line: one per letter as in `CMZ`, or separated by commas as in
+/// `AB,C,DE` when labels are longer than a letter.
+fn crates_arg(labels: &str) -> Vec<Crate> {
+    if labels.contains(',') {
+        labels.split(',').map(|label| label.to_string()).collect()
+    } else {
+        labels.chars().map(|label| label.to_string()).collect()
     }
 }
 
-trait CrateMover {
-    fn execute_moves(self, supply_stack: &mut SupplyStack) -> ();
+fn crane_arg(name: Option<&String>) -> Box<dyn CrateMover> {
+    crane::from_name(name.map_or("9000", |name| name))
 }
 
-struct CrateMover9000 {}
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let path = match args.get(1).map(|arg| arg.as_str()) {
+        Some("validate") => args.get(4),
+        Some("plan") => args.get(6),
+        _ => None,
+    };
+    let file = File::open(path.map_or("supply_stacks/input/input.txt", |path| path)).unwrap();
+    let lines = read_lines(file);
+    let mut supply_stack = match SupplyStack::from_input(&lines) {
+        Ok(supply_stack) => supply_stack,
+        Err(error) => {
+            println!("Invalid drawing: {}", error);
+            std::process::exit(1);
+        }
+    };
 
-impl CrateMover for CrateMover9000 {
-    fn execute_moves(self, supply_stack: &mut SupplyStack) -> () {
-        for m in &supply_stack.moves {
-            for _ in 0..m.quantity {
-                let c = supply_stack.stacks[m.from].pop().unwrap();
-                supply_stack.stacks[m.to].push(c);
+    match args.get(1).map(|arg| arg.as_str()) {
+        Some("debug") => {
+            debug_program(Journal::new(supply_stack, &*crane_arg(args.get(2))));
+            return;
+        }
+        Some("state") => {
+            let step: usize = args[2].parse().unwrap();
+            let crane = crane_arg(args.get(3));
+            let mut journal = Journal::new(supply_stack, &*crane);
+            print_stacks(&journal.state_at(step));
+            return;
+        }
+        Some("render") => {
+            print!("{}", supply_stack);
+            return;
+        }
+        Some("snapshots") => {
+            let crane = crane_arg(args.get(2));
+            let mut journal = Journal::new(supply_stack, &*crane);
+            print_stacks(journal.stacks());
+            while let Some(m) = journal.next_move().copied() {
+                if let Err(reason) = journal.check_next() {
+                    println!();
+                    println!("stopped before {}: {}", m, reason);
+                    break;
+                }
+                journal.redo();
+                println!();
+                println!("{}", m);
+                print_stacks(journal.stacks());
             }
+            return;
         }
-    }
-}
-
-struct CrateMover9001 {}
-
-impl CrateMover for CrateMover9001 {
-    fn execute_moves(self, supply_stack: &mut SupplyStack) -> () {
-        for m in &supply_stack.moves {
-            let len = supply_stack.stacks[m.from].len();
-            let mut crates = supply_stack.stacks[m.from][(len - m.quantity as usize)..].to_vec();
-            supply_stack.stacks[m.to].append(&mut crates);
-            for _ in 0..m.quantity {
-                supply_stack.stacks[m.from].pop().unwrap();
+        Some("validate") => {
+            let mode = match args.get(3).map(|arg| arg.as_str()) {
+                Some("lenient") => Mode::Lenient,
+                _ => Mode::Strict,
+            };
+            let validation = validate::validate(&supply_stack, &*crane_arg(args.get(2)), mode);
+            for violation in &validation.violations {
+                println!(
+                    "line {}: {} - {}",
+                    violation.line, violation.m, violation.reason
+                );
+                print_stacks(&violation.stacks);
+            }
+            if validation.is_ok() {
+                println!(
+                    "Program ran with {} illegal moves - crates at top of the stack: {:?}",
+                    validation.violations.len(),
+                    stack_top(&validation.stacks)
+                );
+            } else {
+                println!(
+                    "Program rejected: {} illegal moves",
+                    validation.violations.len()
+                );
+                std::process::exit(1);
             }
+            return;
         }
+        Some("plan") => {
+            let crane = crane_arg(args.get(2));
+            let goal = match args[3].as_str() {
+                "top" => Goal::Top(crates_arg(&args[4])),
+                "drawing" => {
+                    let target = read_lines(File::open(&args[4]).unwrap());
+                    let drawing_end = target
+                        .iter()
+                        .position(|s| s.is_empty())
+                        .unwrap_or(target.len());
+                    Goal::Arrangement(
+                        SupplyStack::stack_from_input(&target[..drawing_end]).unwrap(),
+                    )
+                }
+                other => panic!("unknown goal {:?}, expected top or drawing", other),
+            };
+            let max_moves = args.get(5).map_or(4, |moves| moves.parse().unwrap());
+            let plan = plan::plan(&supply_stack.stacks, &goal, &*crane, max_moves, 2_000_000);
+            match plan.outcome {
+                Outcome::Found(moves) => {
+                    println!(
+                        "Plan with {} moves ({} arrangements explored):",
+                        moves.len(),
+                        plan.explored
+                    );
+                    for m in &moves {
+                        println!("{}", m);
+                    }
+                    supply_stack.moves = moves;
+                    crane.execute_moves(&mut supply_stack);
+                    print_stacks(&supply_stack.stacks);
+                }
+                Outcome::NotWithin(max_moves) => {
+                    println!("No plan with at most {} moves", max_moves);
+                    std::process::exit(1);
+                }
+                Outcome::GaveUp => {
+                    println!("Gave up after exploring {} arrangements", plan.explored);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Some("run") => {
+            let crane = crane_arg(args.get(2));
+            let cost_model = CostModel {
+                per_lift: args.get(3).map_or(5, |time| time.parse().unwrap()),
+                per_crate: args.get(4).map_or(1, |time| time.parse().unwrap()),
+            };
+            let validation = validate::validate(&supply_stack, &*crane, Mode::Strict);
+            if !validation.is_ok() {
+                println!(
+                    "Program rejected: {} illegal moves, run with `validate` for details",
+                    validation.violations.len()
+                );
+                std::process::exit(1);
+            }
+            let work = validation.work;
+            println!(
+                "Crates at top of the stack: {:?} - {} lifts, {} crates moved, cost {}",
+                stack_top(&validation.stacks),
+                work.lifts,
+                work.crates,
+                cost_model.cost(work)
+            );
+            return;
+        }
+        _ => {}
     }
-}
-
-fn main() {
-    let file = File::open("supply_stacks/input/input.txt").unwrap();
-    let lines = read_lines(file);
-    let mut supply_stack = SupplyStack::from_input(&lines);
 
     let crate_mover_9000 = CrateMover9000 {};
     crate_mover_9000.execute_moves(&mut supply_stack);
@@ -126,7 +244,7 @@ fn main() {
         supply_stack.stack_top()
     );
 
-    let mut supply_stack = SupplyStack::from_input(&lines);
+    let mut supply_stack = SupplyStack::from_input(&lines).unwrap();
     let crate_mover_9001 = CrateMover9001 {};
     crate_mover_9001.execute_moves(&mut supply_stack);
     println!(
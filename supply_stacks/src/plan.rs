@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use crate::crane::CrateMover;
+use crate::{Crate, Move};
+
+/// Where the planner should get the stacks to.
+pub enum Goal {
+    /// Exactly these stacks, bottom crate first.
+    Arrangement(Vec<Vec<Crate>>),
+    /// Any stacks whose top crates, left to right and skipping empty stacks, are these.
+    Top(Vec<Crate>),
+}
+
+impl Goal {
+    fn reached(&self, stacks: &[Vec<Crate>]) -> bool {
+        match self {
+            Goal::Arrangement(target) => stacks == target.as_slice(),
+            Goal::Top(target) => stacks
+                .iter()
+                .filter_map(|stack| stack.last())
+                .eq(target.iter()),
+        }
+    }
+
+    /// Lower bound on the moves still needed. A move only changes two stacks, so at least half
+    /// of the stacks that are off have to be touched.
+    fn estimate(&self, stacks: &[Vec<Crate>]) -> usize {
+        let off = match self {
+            Goal::Arrangement(target) => stacks.iter().zip(target).filter(|(s, t)| s != t).count(),
+            Goal::Top(target) if target.len() == stacks.len() => stacks
+                .iter()
+                .zip(target)
+                .filter(|(stack, c)| stack.last() != Some(c))
+                .count(),
+            // some stacks end up empty, so tops cannot be lined up with the target
+            Goal::Top(_) => !self.reached(stacks) as usize,
+        };
+        off.div_ceil(2)
+    }
+
+    /// Whether the crates on hand can make up the goal at all.
+    fn possible(&self, stacks: &[Vec<Crate>]) -> bool {
+        let mut crates: Vec<&Crate> = stacks.iter().flatten().collect();
+        crates.sort_unstable();
+        match self {
+            Goal::Arrangement(target) => {
+                let mut wanted: Vec<&Crate> = target.iter().flatten().collect();
+                wanted.sort_unstable();
+                target.len() == stacks.len() && crates == wanted
+            }
+            Goal::Top(target) => {
+                target.len() <= stacks.len()
+                    && target.iter().all(|c| {
+                        let wanted = target.iter().filter(|t| *t == c).count();
+                        crates.iter().filter(|k| **k == c).count() >= wanted
+                    })
+            }
+        }
+    }
+}
+
+pub enum Outcome {
+    Found(Vec<Move>),
+    /// No program of at most this many moves reaches the goal.
+    NotWithin(usize),
+    /// The node budget ran out before the search could tell.
+    GaveUp,
+}
+
+pub struct Plan {
+    pub outcome: Outcome,
+    pub explored: usize,
+}
+
+enum Search {
+    Found,
+    /// Smallest estimated total length that went over the bound.
+    Exceeded(usize),
+    OutOfBudget,
+}
+
+struct Planner<'a> {
+    crane: &'a dyn CrateMover,
+    goal: &'a Goal,
+    max_nodes: usize,
+    explored: usize,
+    path: Vec<Move>,
+    // fewest moves any arrangement was reached with in the current iteration
+    seen: HashMap<Vec<Vec<Crate>>, usize>,
+}
+
+impl Planner<'_> {
+    fn search(&mut self, stacks: &[Vec<Crate>], bound: usize) -> Search {
+        let moves = self.path.len();
+        let total = moves + self.goal.estimate(stacks);
+        if total > bound {
+            return Search::Exceeded(total);
+        }
+        if self.goal.reached(stacks) {
+            return Search::Found;
+        }
+        if self.seen.get(stacks).is_some_and(|seen| *seen <= moves) {
+            return Search::Exceeded(usize::MAX);
+        }
+        self.seen.insert(stacks.to_vec(), moves);
+        self.explored += 1;
+        if self.explored > self.max_nodes {
+            return Search::OutOfBudget;
+        }
+
+        let mut next_bound = usize::MAX;
+        for from in 1..=stacks.len() {
+            for to in (1..=stacks.len()).filter(|to| *to != from) {
+                for quantity in 1..=stacks[from - 1].len() {
+                    let m = Move {
+                        quantity: quantity as i64,
+                        from: from as i64,
+                        to: to as i64,
+                    };
+                    let mut next = stacks.to_vec();
+                    self.crane.move_crates(&mut next, &m);
+                    self.path.push(m);
+                    match self.search(&next, bound) {
+                        Search::Exceeded(total) => next_bound = next_bound.min(total),
+                        found_or_out => return found_or_out,
+                    }
+                    self.path.pop();
+                }
+            }
+        }
+        Search::Exceeded(next_bound)
+    }
+}
+
+/// Searches for a shortest crane program of at most `max_moves` moves that gets `stacks` to
+/// `goal`, with iterative deepening on moves made plus `Goal::estimate`. Gives up after
+/// expanding `max_nodes` arrangements.
+pub fn plan(
+    stacks: &[Vec<Crate>],
+    goal: &Goal,
+    crane: &dyn CrateMover,
+    max_moves: usize,
+    max_nodes: usize,
+) -> Plan {
+    let mut planner = Planner {
+        crane,
+        goal,
+        max_nodes,
+        explored: 0,
+        path: vec![],
+        seen: HashMap::new(),
+    };
+    if !goal.possible(stacks) {
+        return Plan {
+            outcome: Outcome::NotWithin(max_moves),
+            explored: 0,
+        };
+    }
+
+    let mut bound = goal.estimate(stacks);
+    let outcome = loop {
+        if bound > max_moves {
+            break Outcome::NotWithin(max_moves);
+        }
+        planner.seen.clear();
+        match planner.search(stacks, bound) {
+            Search::Found => break Outcome::Found(planner.path),
+            Search::OutOfBudget => break Outcome::GaveUp,
+            Search::Exceeded(next_bound) => bound = next_bound,
+        }
+    };
+    Plan {
+        outcome,
+        explored: planner.explored,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::{CrateMover9000, CrateMover9001};
+
+    fn stacks(stacks: &[&str]) -> Vec<Vec<Crate>> {
+        stacks
+            .iter()
+            .map(|stack| stack.chars().map(String::from).collect())
+            .collect()
+    }
+
+    /// The puzzle example before any move.
+    fn example() -> Vec<Vec<Crate>> {
+        stacks(&["ZN", "MCD", "P"])
+    }
+
+    fn replay(stacks: &[Vec<Crate>], crane: &dyn CrateMover, moves: &[Move]) -> Vec<Vec<Crate>> {
+        let mut stacks = stacks.to_vec();
+        for m in moves {
+            crane.move_crates(&mut stacks, m);
+        }
+        stacks
+    }
+
+    fn found(plan: Plan) -> Vec<Move> {
+        match plan.outcome {
+            Outcome::Found(moves) => moves,
+            Outcome::NotWithin(max_moves) => panic!("nothing within {} moves", max_moves),
+            Outcome::GaveUp => panic!("gave up after {} arrangements", plan.explored),
+        }
+    }
+
+    #[test]
+    fn finds_a_shortest_program_for_top_crates() {
+        let goal = Goal::Top(vec!["C".to_string(), "M".to_string(), "Z".to_string()]);
+        let crane = CrateMover9001 {};
+        let moves = found(plan(&example(), &goal, &crane, 10, 1_000_000));
+        assert!(goal.reached(&replay(&example(), &crane, &moves)));
+        // one move less is not enough
+        let shorter = plan(&example(), &goal, &crane, moves.len() - 1, 1_000_000);
+        assert!(matches!(shorter.outcome, Outcome::NotWithin(_)));
+    }
+
+    #[test]
+    fn reaches_the_arrangement_of_the_example_program() {
+        // where the four moves of the puzzle example leave the crates
+        let target = stacks(&["C", "M", "PDNZ"]);
+        let crane = CrateMover9000 {};
+        let moves = found(plan(
+            &example(),
+            &Goal::Arrangement(target.clone()),
+            &crane,
+            4,
+            1_000_000,
+        ));
+        assert!(moves.len() <= 4);
+        assert_eq!(replay(&example(), &crane, &moves), target);
+    }
+
+    #[test]
+    fn impossible_goals_are_not_within_any_bound() {
+        let crane = CrateMover9001 {};
+        let goals = [
+            Goal::Top(vec!["X".to_string()]),
+            Goal::Top(vec!["Z".to_string(); 2]),
+            Goal::Arrangement(stacks(&["ZNMCDP", ""])),
+        ];
+        for goal in goals {
+            let plan = plan(&example(), &goal, &crane, 10, 1_000_000);
+            assert!(matches!(plan.outcome, Outcome::NotWithin(10)));
+            assert_eq!(plan.explored, 0);
+        }
+    }
+
+    #[test]
+    fn gives_up_when_the_node_budget_runs_out() {
+        let goal = Goal::Arrangement(stacks(&["C", "M", "PDNZ"]));
+        let plan = plan(&example(), &goal, &CrateMover9000 {}, 10, 2);
+        assert!(matches!(plan.outcome, Outcome::GaveUp));
+        assert_eq!(plan.explored, 3);
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+
+use crate::{Crate, SupplyStack};
+
+/// Draws stacks the way the puzzle does: one `[X]` cell per stack, bottom crates last, and the
+/// stack numbers underneath. Cells are three characters wide with a space in between, or as
+/// wide as the largest stack number or crate, so every crate and number stays in its column.
+pub fn drawing(stacks: &[Vec<Crate>]) -> Vec<String> {
+    let widest_crate = stacks
+        .iter()
+        .flatten()
+        .map(|c| c.chars().count() + 2)
+        .max()
+        .unwrap_or(0);
+    let width = stacks.len().to_string().len().max(widest_crate).max(3);
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+    let mut lines: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("{:^width$}", format!("[{}]", c)),
+                    None => " ".repeat(width),
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect();
+    lines.push(
+        (1..=stacks.len())
+            .map(|number| format!("{:^width$}", number))
+            .collect::<Vec<String>>()
+            .join(" "),
+    );
+    lines
+}
+
+/// The full puzzle input: drawing, blank line and crane program.
+impl fmt::Display for SupplyStack {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for line in drawing(&self.stacks) {
+            writeln!(f, "{}", line)?;
+        }
+        writeln!(f)?;
+        for m in &self.moves {
+            writeln!(f, "{}", m)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drawing;
+    use utils::Rng;
+
+    /// Crates with labels of one letter, or up to `longest` letters.
+    fn random_stacks(count: usize, longest: u64, rng: &mut Rng) -> Vec<Vec<Crate>> {
+        (0..count)
+            .map(|_| {
+                (0..rng.below(4))
+                    .map(|_| {
+                        (0..1 + rng.below(longest))
+                            .map(|_| (b'A' + rng.below(26) as u8) as char)
+                            .collect()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn draws_the_puzzle_layout() {
+        let stacks = [vec!["Z", "N"], vec!["M", "C", "D"], vec!["P"]]
+            .map(|stack| stack.into_iter().map(String::from).collect());
+        assert_eq!(
+            drawing(&stacks),
+            ["    [D]    ", "[N] [C]    ", "[Z] [M] [P]", " 1   2   3 "]
+        );
+    }
+
+    #[test]
+    fn widens_cells_for_long_labels() {
+        let stacks = [vec!["AB"], vec!["C", "XYZ"]]
+            .map(|stack| stack.into_iter().map(String::from).collect());
+        assert_eq!(
+            drawing(&stacks),
+            ["      [XYZ]", "[AB]   [C] ", "  1     2  "]
+        );
+    }
+
+    #[test]
+    fn parses_back_what_it_draws() {
+        let mut rng = Rng::new(41);
+        for count in [1, 9, 10, 99, 100, 999, 1000, 1234] {
+            let stacks = random_stacks(count, 1, &mut rng);
+            assert_eq!(
+                drawing::parse(&drawing(&stacks)),
+                Ok(stacks),
+                "{} stacks",
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn parses_back_long_labels() {
+        let mut rng = Rng::new(42);
+        for count in [1, 3, 9, 10, 100] {
+            let stacks = random_stacks(count, 6, &mut rng);
+            assert_eq!(
+                drawing::parse(&drawing(&stacks)),
+                Ok(stacks),
+                "{} stacks",
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn program_parses_back_from_its_rendering() {
+        let lines: Vec<String> = [
+            "    [D]",
+            "[N] [C]",
+            "[Z] [M] [P]",
+            " 1   2   3",
+            "",
+            "move 1 from 2 to 1",
+            "move 3 from 1 to 3",
+        ]
+        .map(String::from)
+        .to_vec();
+        let supply_stack = SupplyStack::from_input(&lines).unwrap();
+        let rendered: Vec<String> = supply_stack.to_string().lines().map(String::from).collect();
+        let parsed = SupplyStack::from_input(&rendered).unwrap();
+        assert_eq!(parsed.stacks, supply_stack.stacks);
+        assert_eq!(parsed.moves, supply_stack.moves);
+    }
+}
@@ -0,0 +1,147 @@
+use std::fmt;
+
+use crate::crane::{CrateMover, Work};
+use crate::{Crate, Move, SupplyStack};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// Illegal moves are skipped and make the program fail.
+    Strict,
+    /// Moves asking for too many crates move what is available; other illegal moves are skipped.
+    Lenient,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IllegalMove {
+    NoSuchStack(i64),
+    NegativeQuantity,
+    NotEnoughCrates { available: usize },
+}
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IllegalMove::NoSuchStack(stack) => write!(f, "there is no stack {}", stack),
+            IllegalMove::NegativeQuantity => write!(f, "cannot move a negative number of crates"),
+            IllegalMove::NotEnoughCrates { available } => {
+                write!(f, "only {} crates available", available)
+            }
+        }
+    }
+}
+
+pub struct Violation {
+    pub line: usize,
+    pub m: Move,
+    pub reason: IllegalMove,
+    /// Stacks right before the illegal move.
+    pub stacks: Vec<Vec<Crate>>,
+}
+
+pub struct Validation {
+    pub mode: Mode,
+    pub violations: Vec<Violation>,
+    pub stacks: Vec<Vec<Crate>>,
+    pub work: Work,
+}
+
+impl Validation {
+    pub fn is_ok(&self) -> bool {
+        self.mode == Mode::Lenient || self.violations.is_empty()
+    }
+}
+
+/// Whether `m` can be carried out on `stacks` as they are.
+pub fn check(stacks: &[Vec<Crate>], m: &Move) -> Result<(), IllegalMove> {
+    for stack in [m.from, m.to] {
+        if stack < 1 || stack > stacks.len() as i64 {
+            return Err(IllegalMove::NoSuchStack(stack));
+        }
+    }
+    if m.quantity < 0 {
+        return Err(IllegalMove::NegativeQuantity);
+    }
+    let available = stacks[m.source()].len();
+    if m.quantity as usize > available {
+        return Err(IllegalMove::NotEnoughCrates { available });
+    }
+    Ok(())
+}
+
+/// Simulates the whole program with `crane`, recording every move that is not legal at the
+/// point it is reached.
+pub fn validate(supply_stack: &SupplyStack, crane: &dyn CrateMover, mode: Mode) -> Validation {
+    let mut stacks = supply_stack.stacks.clone();
+    let mut violations = vec![];
+    let mut work = Work::default();
+
+    for (index, m) in supply_stack.moves.iter().enumerate() {
+        match check(&stacks, m) {
+            Ok(()) => work += crane.move_crates(&mut stacks, m),
+            Err(reason) => {
+                let lenient_move = match reason {
+                    IllegalMove::NotEnoughCrates { available } if mode == Mode::Lenient => {
+                        Some(Move {
+                            quantity: available as i64,
+                            ..*m
+                        })
+                    }
+                    _ => None,
+                };
+                violations.push(Violation {
+                    line: supply_stack.first_move_line + index,
+                    m: *m,
+                    reason,
+                    stacks: stacks.clone(),
+                });
+                if let Some(lenient_move) = lenient_move {
+                    work += crane.move_crates(&mut stacks, &lenient_move);
+                }
+            }
+        }
+    }
+
+    Validation {
+        mode,
+        violations,
+        stacks,
+        work,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::CrateMover9000;
+
+    #[test]
+    fn reports_stacks_that_do_not_exist_with_their_line() {
+        let supply_stack = SupplyStack {
+            stacks: vec![vec!["A".to_string(), "B".to_string()], vec![]],
+            moves: [
+                "move 1 from -1 to 2",
+                "move 1 from 1 to 0",
+                "move 3 from 1 to 2",
+            ]
+            .iter()
+            .map(|line| Move::new(line))
+            .collect(),
+            first_move_line: 4,
+        };
+        let validation = validate(&supply_stack, &CrateMover9000 {}, Mode::Strict);
+        let found: Vec<(usize, &IllegalMove)> = validation
+            .violations
+            .iter()
+            .map(|violation| (violation.line, &violation.reason))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (4, &IllegalMove::NoSuchStack(-1)),
+                (5, &IllegalMove::NoSuchStack(0)),
+                (6, &IllegalMove::NotEnoughCrates { available: 2 }),
+            ]
+        );
+        assert!(!validation.is_ok());
+    }
+}
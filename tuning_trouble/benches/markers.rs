@@ -0,0 +1,47 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tuning_trouble::ascii::first_marker;
+use tuning_trouble::buffer::MarkerExt;
+use utils::Rng;
+
+const LENGTH: usize = 4 << 20;
+
+/// `LENGTH` random bytes drawn from `symbols`.
+fn generate(symbols: &[u8], rng: &mut Rng) -> Vec<u8> {
+    (0..LENGTH)
+        .map(|_| symbols[rng.below(symbols.len() as u64) as usize])
+        .collect()
+}
+
+/// Windows wider than the alphabet so no marker exists and the whole stream is read.
+fn markers(c: &mut Criterion) {
+    let mut rng = Rng::new(2022);
+    let streams = [
+        (
+            "lowercase 12 letters",
+            generate(b"abcdefghijkl", &mut rng),
+            14,
+        ),
+        ("lowercase 3 letters", generate(b"abc", &mut rng), 4),
+        (
+            "mixed case fallback",
+            generate(b"ABCDEFabcdef", &mut rng),
+            14,
+        ),
+    ];
+
+    let mut group = c.benchmark_group("first marker");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(LENGTH as u64));
+    for (name, stream, size) in &streams {
+        group.bench_with_input(BenchmarkId::new("buffer", name), stream, |b, stream| {
+            b.iter(|| stream.iter().copied().markers(*size).next())
+        });
+        group.bench_with_input(BenchmarkId::new("ascii", name), stream, |b, stream| {
+            b.iter(|| first_marker(stream, *size))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, markers);
+criterion_main!(benches);
@@ -0,0 +1,69 @@
+use crate::buffer::MarkerExt;
+
+/// Position right after the first window of `size` distinct bytes, like
+/// `bytes.iter().copied().markers(size).next()`.
+///
+/// Lowercase ASCII streams keep the window as a 26-bit mask with each letter's bit flipped as it
+/// enters and leaves, so a bit is set for letters seen an odd number of times. The window is a
+/// marker when `size` bits are set, since `size` letters can only do that by all being
+/// different. Any other byte sends the whole stream through the generic `Buffer` instead.
+pub fn first_marker(bytes: &[u8], size: usize) -> Option<usize> {
+    let generic = || bytes.iter().copied().markers(size).next();
+    if size == 0 || size > 26 {
+        return generic();
+    }
+
+    let mut window = 0u32;
+    for (index, byte) in bytes.iter().enumerate() {
+        if !byte.is_ascii_lowercase() {
+            return generic();
+        }
+        window ^= 1 << (byte - b'a');
+        if index >= size {
+            window ^= 1 << (bytes[index - size] - b'a');
+        }
+        if window.count_ones() as usize == size {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::Rng;
+
+    fn generate(length: usize, symbols: &[u8], rng: &mut Rng) -> Vec<u8> {
+        (0..length)
+            .map(|_| symbols[rng.below(symbols.len() as u64) as usize])
+            .collect()
+    }
+
+    #[test]
+    fn agrees_with_the_generic_buffer() {
+        let mut rng = Rng::new(2022);
+        let lowercase: Vec<u8> = (b'a'..=b'z').collect();
+        let mixed_case: Vec<u8> = (b'A'..=b'F').chain(b'a'..=b'f').collect();
+        for _ in 0..200 {
+            let alphabet = 1 + rng.below(26) as usize;
+            let length = rng.below(2_000) as usize;
+            for symbols in [&lowercase[..alphabet], &mixed_case[..alphabet.min(12)]] {
+                let stream = generate(length, symbols, &mut rng);
+                for size in [1, 4, 14, 26, 27] {
+                    assert_eq!(
+                        first_marker(&stream, size),
+                        stream.iter().copied().markers(size).next()
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn falls_back_for_other_bytes() {
+        assert_eq!(first_marker(b"aaaA", 2), Some(4));
+        assert_eq!(first_marker(b"abc", 4), None);
+        assert_eq!(first_marker(b"", 1), None);
+    }
+}
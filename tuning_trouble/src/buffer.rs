@@ -0,0 +1,270 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// The last `size` symbols read, with how often each occurs so distinctness is known without
+/// looking at the whole window.
+pub struct Buffer<T = char> {
+    symbols: VecDeque<T>,
+    counts: HashMap<T, usize>,
+    distinct: usize,
+    size: usize,
+}
+
+impl<T: Copy + Eq + Hash> Buffer<T> {
+    pub fn new(size: usize) -> Self {
+        Buffer {
+            symbols: VecDeque::with_capacity(size + 1),
+            counts: HashMap::new(),
+            distinct: 0,
+            size,
+        }
+    }
+
+    pub fn read(&mut self, symbol: T) {
+        self.symbols.push_back(symbol);
+        let count = self.counts.entry(symbol).or_insert(0);
+        *count += 1;
+        if *count == 1 {
+            self.distinct += 1;
+        }
+        if self.symbols.len() > self.size {
+            let old = self.symbols.pop_front().unwrap();
+            let count = self.counts.get_mut(&old).unwrap();
+            *count -= 1;
+            if *count == 0 {
+                self.counts.remove(&old);
+                self.distinct -= 1;
+            }
+        }
+    }
+
+    /// The symbols in the window, oldest first.
+    pub fn window(&self) -> Vec<T> {
+        self.symbols.iter().copied().collect()
+    }
+
+    /// Whether the window is full and holds `size` different symbols.
+    pub fn start_marker(&self) -> bool {
+        self.distinct == self.size
+    }
+
+    /// Symbols that would have to be replaced to turn a full window into a marker, one for
+    /// every repeat of a symbol already in it.
+    pub fn substitutions(&self) -> Option<usize> {
+        (self.symbols.len() == self.size).then(|| self.size - self.distinct)
+    }
+}
+
+/// Positions in a stream, counted from 1, right after every window of distinct symbols.
+pub struct Markers<I: Iterator> {
+    symbols: I,
+    buffer: Buffer<I::Item>,
+    read: usize,
+}
+
+impl<I> Iterator for Markers<I>
+where
+    I: Iterator,
+    I::Item: Copy + Eq + Hash,
+{
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        for symbol in self.symbols.by_ref() {
+            self.buffer.read(symbol);
+            self.read += 1;
+            if self.buffer.start_marker() {
+                return Some(self.read);
+            }
+        }
+        None
+    }
+}
+
+/// A window of `size` distinct symbols ending right after the `position`-th symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Marker<T = char> {
+    pub size: usize,
+    pub position: usize,
+    pub window: Vec<T>,
+}
+
+/// Every marker of several window sizes, found in one pass over the stream. Markers ending at
+/// the same position come out in the order the sizes were given.
+pub struct AllMarkers<I: Iterator> {
+    symbols: I,
+    buffers: Vec<Buffer<I::Item>>,
+    read: usize,
+    found: VecDeque<Marker<I::Item>>,
+}
+
+impl<I> Iterator for AllMarkers<I>
+where
+    I: Iterator,
+    I::Item: Copy + Eq + Hash,
+{
+    type Item = Marker<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.found.is_empty() {
+            let symbol = self.symbols.next()?;
+            self.read += 1;
+            for buffer in self.buffers.iter_mut() {
+                buffer.read(symbol);
+                if buffer.start_marker() {
+                    self.found.push_back(Marker {
+                        size: buffer.size,
+                        position: self.read,
+                        window: buffer.window(),
+                    });
+                }
+            }
+        }
+        self.found.pop_front()
+    }
+}
+
+/// A window that is a marker once at most the allowed number of symbols are replaced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoisyMarker {
+    pub position: usize,
+    pub substitutions: usize,
+    /// Share of the window that is distinct as received, 1.0 for an exact marker.
+    pub confidence: f64,
+}
+
+/// Every window with at least `size - errors` distinct symbols, which is the same as needing at
+/// most `errors` substituted symbols to become a marker.
+pub struct NoisyMarkers<I: Iterator> {
+    symbols: I,
+    buffer: Buffer<I::Item>,
+    errors: usize,
+    read: usize,
+}
+
+impl<I> Iterator for NoisyMarkers<I>
+where
+    I: Iterator,
+    I::Item: Copy + Eq + Hash,
+{
+    type Item = NoisyMarker;
+
+    fn next(&mut self) -> Option<NoisyMarker> {
+        for symbol in self.symbols.by_ref() {
+            self.buffer.read(symbol);
+            self.read += 1;
+            match self.buffer.substitutions() {
+                Some(substitutions) if substitutions <= self.errors => {
+                    return Some(NoisyMarker {
+                        position: self.read,
+                        substitutions,
+                        confidence: 1.0 - substitutions as f64 / self.buffer.size as f64,
+                    })
+                }
+                _ => continue,
+            }
+        }
+        None
+    }
+}
+
+pub trait MarkerExt: Iterator + Sized {
+    /// Marker positions for windows of `size` symbols, e.g. `line.chars().markers(4)`.
+    fn markers(self, size: usize) -> Markers<Self>
+    where
+        Self::Item: Copy + Eq + Hash,
+    {
+        Markers {
+            symbols: self,
+            buffer: Buffer::new(size),
+            read: 0,
+        }
+    }
+
+    /// Like `markers`, but tolerating up to `errors` corrupted symbols per window.
+    fn noisy_markers(self, size: usize, errors: usize) -> NoisyMarkers<Self>
+    where
+        Self::Item: Copy + Eq + Hash,
+    {
+        NoisyMarkers {
+            symbols: self,
+            buffer: Buffer::new(size),
+            errors,
+            read: 0,
+        }
+    }
+
+    /// Every marker for each of `sizes`, reading the stream once.
+    fn all_markers(self, sizes: &[usize]) -> AllMarkers<Self>
+    where
+        Self::Item: Copy + Eq + Hash,
+    {
+        AllMarkers {
+            symbols: self,
+            buffers: sizes.iter().map(|size| Buffer::new(*size)).collect(),
+            read: 0,
+            found: VecDeque::new(),
+        }
+    }
+}
+
+impl<I: Iterator> MarkerExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::Rng;
+
+    fn random_stream(length: usize, alphabet: u64, rng: &mut Rng) -> String {
+        (0..length)
+            .map(|_| (b'a' + rng.below(alphabet) as u8) as char)
+            .collect()
+    }
+
+    #[test]
+    fn noisy_markers_without_errors_are_the_exact_markers() {
+        let mut rng = Rng::new(2022);
+        for alphabet in [4, 8, 16, 26] {
+            let stream = random_stream(20_000, alphabet, &mut rng);
+            for size in [4, 14] {
+                let exact: Vec<usize> = stream.chars().markers(size).collect();
+                let noisy: Vec<NoisyMarker> = stream.chars().noisy_markers(size, 0).collect();
+                assert!(noisy.iter().all(|marker| marker.confidence == 1.0));
+                assert_eq!(
+                    noisy
+                        .iter()
+                        .map(|marker| marker.position)
+                        .collect::<Vec<usize>>(),
+                    exact
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn noisy_markers_tolerate_substituted_symbols() {
+        // "aabc" and "abca" each need one substitution, "bcad" none
+        let markers: Vec<NoisyMarker> = "aabcad".chars().noisy_markers(4, 1).collect();
+        assert_eq!(
+            markers,
+            vec![
+                NoisyMarker {
+                    position: 4,
+                    substitutions: 1,
+                    confidence: 0.75
+                },
+                NoisyMarker {
+                    position: 5,
+                    substitutions: 1,
+                    confidence: 0.75
+                },
+                NoisyMarker {
+                    position: 6,
+                    substitutions: 0,
+                    confidence: 1.0
+                },
+            ]
+        );
+        assert_eq!("aabcad".chars().markers(4).collect::<Vec<usize>>(), vec![6]);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod ascii;
+pub mod buffer;
+pub mod stream;
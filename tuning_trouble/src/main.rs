@@ -1,55 +1,94 @@
-use std::collections::{HashSet, VecDeque};
 use std::fs::File;
+use std::io;
+use std::net::TcpListener;
 use utils::read_lines;
 
-struct Buffer {
-    chars: VecDeque<char>,
-    size: usize,
-}
+use tuning_trouble::buffer::MarkerExt;
+use tuning_trouble::stream::{self, Decoder, Event};
 
-impl Buffer {
-    pub fn new(size: usize) -> Self {
-        Buffer {
-            chars: VecDeque::new(),
-            size,
-        }
+fn print_event(event: Event) {
+    match event {
+        Event::PacketStart(position) => println!("start of packet marker: {}", position),
+        Event::MessageStart(position) => println!("start of message marker: {}", position),
+        Event::Message(message) => println!("message: {:?}", message),
     }
+}
 
-    pub fn read(&mut self, c: char) {
-        self.chars.push_back(c);
-        if self.chars.len() > self.size {
-            self.chars.pop_front();
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let mut decoder = Decoder::new(4, 14);
+    match args.get(1).map(|arg| arg.as_str()) {
+        Some("stream") => {
+            stream::decode(io::stdin().lock(), &mut decoder, print_event).unwrap();
+            return;
         }
+        Some("listen") => {
+            match (args[2].as_str(), &args[3]) {
+                ("tcp", address) => {
+                    let (socket, peer) = TcpListener::bind(address).unwrap().accept().unwrap();
+                    println!("connection from {}", peer);
+                    stream::decode(socket, &mut decoder, print_event).unwrap();
+                }
+                #[cfg(unix)]
+                ("unix", path) => {
+                    let listener = std::os::unix::net::UnixListener::bind(path).unwrap();
+                    let (socket, _) = listener.accept().unwrap();
+                    stream::decode(socket, &mut decoder, print_event).unwrap();
+                    std::fs::remove_file(path).unwrap();
+                }
+                (kind, _) => panic!("unknown socket type {:?}", kind),
+            }
+            return;
+        }
+        _ => {}
     }
 
-    pub fn start_marker(&self) -> bool {
-        let set: HashSet<char> = HashSet::from_iter(self.chars.iter().cloned());
-        set.len() == self.size
-    }
-}
-
-fn main() {
     let file = File::open("tuning_trouble/input/input.txt").unwrap();
     let lines = read_lines(file);
     let line = lines.first().unwrap();
 
-    let mut buffer = Buffer::new(4);
-
-    for (index, c) in line.chars().enumerate() {
-        buffer.read(c);
-        if buffer.start_marker() {
-            println!("Part 1 - start of packet marker: {:?}", index + 1usize);
-            break;
+    if let Some("noisy") = args.get(1).map(|arg| arg.as_str()) {
+        let errors = args.get(2).map_or(1, |errors| errors.parse().unwrap());
+        for size in [4, 14] {
+            match line.chars().noisy_markers(size, errors).next() {
+                Some(marker) => println!(
+                    "size {:>2} - marker ends at {:>4} ({} substituted, confidence {:.2})",
+                    size, marker.position, marker.substitutions, marker.confidence
+                ),
+                None => println!("size {:>2} - no marker within {} errors", size, errors),
+            }
         }
+        return;
     }
 
-    let mut buffer = Buffer::new(14);
+    if let Some("all") = args.get(1).map(|arg| arg.as_str()) {
+        let sizes: Vec<usize> = match args.len() {
+            2 => vec![4, 14],
+            _ => args[2..].iter().map(|size| size.parse().unwrap()).collect(),
+        };
+        for marker in line.chars().all_markers(&sizes) {
+            println!(
+                "size {:>2} - marker ends at {:>5}: {}",
+                marker.size,
+                marker.position,
+                marker.window.iter().collect::<String>()
+            );
+        }
+        return;
+    }
 
-    for (index, c) in line.chars().enumerate() {
-        buffer.read(c);
-        if buffer.start_marker() {
-            println!("Part 2 - start of message marker: {:?}", index + 1usize);
+    // first marker of each size, scanning the datastream once
+    let sizes = [4, 14];
+    let mut first: Vec<Option<usize>> = vec![None; sizes.len()];
+    for marker in line.chars().all_markers(&sizes) {
+        let index = sizes.iter().position(|size| *size == marker.size).unwrap();
+        first[index].get_or_insert(marker.position);
+        if first.iter().all(|position| position.is_some()) {
             break;
         }
     }
+
+    println!("Part 1 - start of packet marker: {:?}", first[0].unwrap());
+
+    println!("Part 2 - start of message marker: {:?}", first[1].unwrap());
 }
@@ -0,0 +1,201 @@
+use std::io::{self, Read};
+
+use crate::buffer::Buffer;
+
+/// What the decoder noticed, with positions counted in bytes from the start of the stream.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Event {
+    PacketStart(usize),
+    MessageStart(usize),
+    /// Everything after a start-of-message marker up to the end of its line.
+    Message(String),
+}
+
+enum State {
+    Packet(Buffer<u8>),
+    Message(Buffer<u8>),
+    Body(Vec<u8>),
+}
+
+/// Splits a datastream into frames: a start-of-packet marker, then a start-of-message marker,
+/// then the message itself, ended by a newline. A newline before the message starts drops the
+/// frame, and the next one is searched from scratch.
+pub struct Decoder {
+    packet_size: usize,
+    message_size: usize,
+    state: State,
+    read: usize,
+}
+
+impl Decoder {
+    pub fn new(packet_size: usize, message_size: usize) -> Self {
+        Decoder {
+            packet_size,
+            message_size,
+            state: State::Packet(Buffer::new(packet_size)),
+            read: 0,
+        }
+    }
+
+    pub fn feed(&mut self, byte: u8) -> Option<Event> {
+        self.read += 1;
+        if byte == b'\n' {
+            let state = std::mem::replace(
+                &mut self.state,
+                State::Packet(Buffer::new(self.packet_size)),
+            );
+            return match state {
+                State::Body(body) => {
+                    Some(Event::Message(String::from_utf8_lossy(&body).into_owned()))
+                }
+                _ => None,
+            };
+        }
+        match &mut self.state {
+            State::Packet(buffer) => {
+                buffer.read(byte);
+                if buffer.start_marker() {
+                    self.state = State::Message(Buffer::new(self.message_size));
+                    return Some(Event::PacketStart(self.read));
+                }
+            }
+            State::Message(buffer) => {
+                buffer.read(byte);
+                if buffer.start_marker() {
+                    self.state = State::Body(vec![]);
+                    return Some(Event::MessageStart(self.read));
+                }
+            }
+            State::Body(body) => body.push(byte),
+        }
+        None
+    }
+
+    /// Emits a message still open when the stream ends without a final newline.
+    pub fn finish(&mut self) -> Option<Event> {
+        self.feed(b'\n')
+    }
+}
+
+/// Feeds `reader` through `decoder` as bytes arrive, handing every event to `emit`.
+pub fn decode(
+    mut reader: impl Read,
+    decoder: &mut Decoder,
+    mut emit: impl FnMut(Event),
+) -> io::Result<()> {
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        for byte in &chunk[..read] {
+            if let Some(event) = decoder.feed(*byte) {
+                emit(event);
+            }
+        }
+    }
+    if let Some(event) = decoder.finish() {
+        emit(event);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+    use utils::Rng;
+
+    const MESSAGES: &[&str] = &[
+        "hello from the device",
+        "calibration complete",
+        "",
+        "signal strength: 4 of 5",
+        "goodbye",
+    ];
+
+    /// A datastream carrying `MESSAGES`, each frame led by noise with too few distinct symbols to
+    /// look like a marker.
+    fn datastream(rng: &mut Rng) -> Vec<u8> {
+        let mut stream = vec![];
+        for message in MESSAGES {
+            for _ in 0..rng.below(40) {
+                stream.push(b"ab"[rng.below(2) as usize]);
+            }
+            stream.extend(b"abcd");
+            stream.extend(b"efghijklmnopqr");
+            stream.extend(message.as_bytes());
+            stream.push(b'\n');
+        }
+        stream
+    }
+
+    /// Writes `data` in small, uneven chunks so the decoder sees frames split across reads.
+    fn send(mut writer: impl Write, data: Vec<u8>, seed: u64) {
+        let mut rng = Rng::new(seed);
+        let mut rest = &data[..];
+        while !rest.is_empty() {
+            let (chunk, tail) = rest.split_at(rest.len().min(1 + rng.below(16) as usize));
+            writer.write_all(chunk).unwrap();
+            writer.flush().unwrap();
+            rest = tail;
+        }
+    }
+
+    fn received(reader: impl Read) -> Vec<String> {
+        let mut messages = vec![];
+        decode(reader, &mut Decoder::new(4, 14), |event| {
+            if let Event::Message(message) = event {
+                messages.push(message);
+            }
+        })
+        .unwrap();
+        messages
+    }
+
+    #[test]
+    fn decodes_messages_over_tcp() {
+        let data = datastream(&mut Rng::new(2022));
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let sender = thread::spawn(move || send(TcpStream::connect(address).unwrap(), data, 1));
+        assert_eq!(received(listener.accept().unwrap().0), MESSAGES);
+        sender.join().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn decodes_messages_over_a_unix_socket() {
+        use std::os::unix::net::{UnixListener, UnixStream};
+
+        let data = datastream(&mut Rng::new(2023));
+        let path = std::env::temp_dir().join(format!("tuning_trouble_{}.sock", std::process::id()));
+        let listener = UnixListener::bind(&path).unwrap();
+        let socket = path.clone();
+        let sender = thread::spawn(move || send(UnixStream::connect(socket).unwrap(), data, 2));
+        assert_eq!(received(listener.accept().unwrap().0), MESSAGES);
+        sender.join().unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reports_marker_positions_and_drops_unfinished_frames() {
+        let mut events = vec![];
+        let data = b"aabcd\nabcdefghijklmnopqrhi\nabab".as_slice();
+        decode(data, &mut Decoder::new(4, 14), |event| events.push(event)).unwrap();
+        assert_eq!(
+            events,
+            vec![
+                Event::PacketStart(5),
+                Event::PacketStart(10),
+                Event::MessageStart(24),
+                Event::Message("hi".to_string()),
+            ]
+        );
+    }
+}